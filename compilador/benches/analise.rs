@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use compilador::{
+    gerador::Gerador,
+    lexico::Lexico,
+    semantico::{visitor::Visitor, Semantico},
+    sintatico::parser::Parser,
+};
+
+// quantidades aproximadas de linhas dos programas sinteticos, cuja razao entre os tempos indica o crescimento de cada etapa
+const LINHAS: [usize; 2] = [10_000, 50_000];
+// cada programa eh compilado algumas vezes, e o menor tempo de cada etapa descarta variacoes causadas pelo sistema
+const REPETICOES: usize = 5;
+
+/// retorna programa LA sintetico com cerca de `linhas` linhas, formado por funcoes encadeadas
+/// que usam registros, vetores, lacos, condicionais e selecoes
+fn programa(linhas: usize) -> String {
+    let mut codigo = "tipo Ponto: registro\n    x, y: inteiro\nfim_registro\n\ndeclare total: inteiro\n\n".to_string();
+    let funcoes = linhas / 28;
+    for i in 0..funcoes {
+        let anterior = if i == 0 { "0".to_string() } else { format!("f{}(a, b)", i - 1) };
+        codigo += &format!("\
+funcao f{i}(a: inteiro, b: inteiro): inteiro
+    declare j, soma: inteiro
+    declare v[10]: inteiro
+    declare p: Ponto
+    soma <- 0
+    para j <- 0 ate 9 faca
+        v[j] <- a * j + b
+        soma <- soma + v[j]
+    fim_para
+    p.x <- soma
+    p.y <- a - b
+    se p.x > p.y entao
+        soma <- soma - p.y
+    senao
+        soma <- soma + p.x
+    fim_se
+    enquanto soma > 100 faca
+        soma <- soma div 2
+    fim_enquanto
+    caso a seja
+        0: soma <- soma + 1
+        1..5: soma <- soma + 2
+    senao
+        soma <- soma + 3
+    fim_caso
+    retorne soma + {anterior}
+fim_funcao
+
+");
+    }
+    codigo += &format!("\
+algoritmo
+    total <- f{}(1, 2)
+    escreva(total)
+fim_algoritmo
+", funcoes - 1);
+    codigo
+}
+
+/// executa `etapa`, retornando seu resultado e o tempo gasto
+fn medir<T>(etapa: impl FnOnce() -> T) -> (T, Duration) {
+    let inicio = Instant::now();
+    let resultado = etapa();
+    (resultado, inicio.elapsed())
+}
+
+/// compila o programa `codigo`, retornando o tempo gasto por analises lexica e sintatica,
+/// analise semantica e geracao de codigo
+fn compilar(codigo: &str, linhas: usize) -> [Duration; 3] {
+    // o identificador do processo evita que execucoes simultaneas sobrescrevam o arquivo uma da outra
+    let arquivo = std::env::temp_dir().join(format!("compilador_bench_analise_{}_{}.alg", std::process::id(), linhas));
+    std::fs::write(&arquivo, codigo).unwrap();
+
+    let (ast, sintatico) = medir(|| Parser::new(Lexico::new(arquivo.to_str().unwrap())).programa());
+    std::fs::remove_file(&arquivo).unwrap();
+
+    let (semantico, analise) = medir(|| {
+        let mut semantico = Semantico::new();
+        semantico.traverse(&ast);
+        semantico
+    });
+    assert!(semantico.get_erros().is_empty(), "programa sintetico deve ser valido: {:?}", semantico.get_erros().first());
+
+    let (_, geracao) = medir(|| {
+        let mut gerador = Gerador::new(semantico.get_tipos());
+        gerador.visit(&ast);
+        gerador
+    });
+
+    [sintatico, analise, geracao]
+}
+
+/// compila um programa sintetico com cerca de `linhas` linhas `REPETICOES` vezes, retornando o menor tempo de cada etapa
+fn medir_programa(linhas: usize) -> [Duration; 3] {
+    let codigo = programa(linhas);
+    let mut tempos = compilar(&codigo, linhas);
+    for _ in 1..REPETICOES {
+        let repeticao = compilar(&codigo, linhas);
+        for (tempo, novo) in tempos.iter_mut().zip(repeticao) {
+            *tempo = (*tempo).min(novo);
+        }
+    }
+
+    println!("programa sintetico com {} linhas", codigo.lines().count());
+    println!("    lexico e sintatico: {:?}", tempos[0]);
+    println!("    semantico: {:?}", tempos[1]);
+    println!("    gerador: {:?}", tempos[2]);
+    tempos
+}
+
+fn main() {
+    let [menor, maior] = LINHAS.map(medir_programa);
+
+    // com crescimento linear, cada razao fica proxima da razao entre os tamanhos
+    println!("razao entre os tempos ({} para {} linhas, esperada em torno de {}):", LINHAS[0], LINHAS[1], LINHAS[1] / LINHAS[0]);
+    for (etapa, (menor, maior)) in ["lexico e sintatico", "semantico", "gerador"].iter().zip(menor.iter().zip(&maior)) {
+        println!("    {}: {:.1}", etapa, maior.as_secs_f64() / menor.as_secs_f64());
+    }
+}
@@ -12,7 +12,8 @@ fn main() {
 
     while next.tipo() != TipoToken::Fim {
         if next.tipo() == TipoToken::Erro {
-            file_out.write_all(next.lexema().as_bytes()).unwrap();
+            let erro = lex.diagnosticos().last().unwrap().renderizar();
+            file_out.write_all(erro.as_bytes()).unwrap();
             break;
         }
         file_out.write_all((next.to_string() + "\n").as_bytes()).unwrap();
@@ -14,8 +14,8 @@ fn main() {
     let mut parser = Parser::new(lex);
     let ast = parser.programa();
     if ast.is_erro() {
-        let mensagem = ast.get_erro().unwrap();
-        file_out.write_all(mensagem.as_bytes()).unwrap();
+        let erro = ast.get_erro().unwrap().renderizar();
+        file_out.write_all(erro.as_bytes()).unwrap();
     }
     file_out.write_all("Fim da compilacao\n".as_bytes()).unwrap();
 }
\ No newline at end of file
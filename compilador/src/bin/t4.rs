@@ -3,6 +3,7 @@ use std::fs::File;
 use std::io::Write;
 
 use compilador::{
+    diagnostico::relatar,
     lexico::Lexico,
     sintatico::parser::Parser,
     semantico::{visitor::Visitor, Semantico},
@@ -14,10 +15,9 @@ fn main() {
     let lex = Lexico::new(&args[1]);
     let mut parser = Parser::new(lex);
     let ast = parser.programa();
-    let mut semantico = Semantico::new();
+    let mut semantico = Semantico::com_argumentos(&args);
     semantico.traverse(&ast);
-    for erro in semantico.get_erros() {
-        file_out.write_all(erro.as_bytes()).unwrap();
-    }
+    let relatorio = relatar(&semantico.get_avisos(), &semantico.get_erros());
+    file_out.write_all(relatorio.as_bytes()).unwrap();
     file_out.write_all("Fim da compilacao\n".as_bytes()).unwrap();
 }
\ No newline at end of file
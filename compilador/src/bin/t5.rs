@@ -3,6 +3,7 @@ use std::fs::File;
 use std::io::Write;
 
 use compilador::{
+    diagnostico::relatar,
     lexico::Lexico,
     sintatico::parser::Parser,
     semantico::{visitor::Visitor, Semantico},
@@ -15,17 +16,16 @@ fn main() {
     let lex = Lexico::new(&args[1]);
     let mut parser = Parser::new(lex);
     let ast = parser.programa();
-    let mut semantico = Semantico::new();
+    let mut semantico = Semantico::com_argumentos(&args);
     semantico.traverse(&ast);
     let erros = semantico.get_erros();
+    let relatorio = relatar(&semantico.get_avisos(), &erros);
 
-    if erros.len() > 0 {
-        for erro in erros {
-            file_out.write_all(erro.as_bytes()).unwrap();
-        }
+    if !erros.is_empty() {
+        file_out.write_all(relatorio.as_bytes()).unwrap();
         file_out.write_all("Fim da compilacao\n".as_bytes()).unwrap();
     } else {
-        let mut gerador = Gerador::new();
+        let mut gerador = Gerador::new(semantico.get_tipos());
         gerador.visit(&ast);
         file_out.write_all(gerador.saida().as_bytes()).unwrap();
     }
@@ -0,0 +1,169 @@
+/// tipo de problema relatado por um diagnostico
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Codigo {
+    // erros lexicos
+    SimboloNaoIdentificado,
+    CadeiaNaoFechada,
+    ComentarioNaoFechado,
+    CaractereNaoFechado,
+    CaractereVazio,
+
+    // erros sintaticos
+    ErroSintatico,
+
+    // erros semanticos
+    IdentificadorJaDeclarado,
+    IdentificadorNaoDeclarado,
+    TipoNaoDeclarado,
+    AtribuicaoIncompativel,
+    NaoAtribuivel,
+    NaoEhValor,
+    InicializacaoIncompativel,
+    QuantidadeDeValores,
+    IndiceInvalido,
+    DimensoesIncompativeis,
+    IndiceForaDosLimites,
+    CampoInicializado,
+    ParametrosIncompativeis,
+    ProcedimentoEmExpressao,
+    ChamadaInvalida,
+    ResultadoDescartado,
+    ModoDeAbertura,
+    RetorneNaoPermitido,
+    FormatoInvalido,
+    AfirmeNaoLogico,
+    ComparacaoIncompativel,
+    CondicaoNaoLogica,
+    SeletorInvalido,
+    RotuloInvalido,
+    ParaNaoInteiro,
+    ConstanteNaoEnumerada,
+    RetornoAusente,
+    RetornoIncompativel,
+
+    // avisos
+    VariavelNaoLida,
+    ParametroNaoUsado,
+    TipoNaoUsado,
+    ConstanteNaoUsada,
+    LeituraSemValor,
+    ComandoInalcancavel,
+    CasoIncompleto,
+}
+
+/// gravidade de um diagnostico
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severidade {
+    Erro,
+    Aviso,
+}
+
+/// trecho do arquivo fonte a que um diagnostico se refere\
+/// linha e coluna comecam em 1, e sao 0 quando desconhecidas, como em simbolos da biblioteca
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Trecho {
+    pub linha: u32,
+    pub coluna: u32,
+    // quantidade de caracteres do trecho
+    pub comprimento: u32,
+}
+
+impl Trecho {
+
+    /// retorna trecho de `comprimento` caracteres que comeca na coluna `coluna` da linha `linha`
+    pub fn new(linha: u32, coluna: u32, comprimento: u32) -> Self {
+        Self { linha, coluna, comprimento }
+    }
+}
+
+/// problema encontrado em alguma etapa da compilacao
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostico {
+    pub codigo: Codigo,
+    pub severidade: Severidade,
+    pub trecho: Trecho,
+    pub mensagem: String,
+    pub notas: Vec<String>,
+    pub sugestoes: Vec<String>,
+}
+
+impl Diagnostico {
+
+    /// retorna novo diagnostico de erro em `trecho`
+    pub fn erro(codigo: Codigo, trecho: Trecho, mensagem: String) -> Self {
+        Self {
+            codigo,
+            severidade: Severidade::Erro,
+            trecho,
+            mensagem,
+            notas: vec![],
+            sugestoes: vec![],
+        }
+    }
+
+    /// retorna novo diagnostico de aviso em `trecho`
+    pub fn aviso(codigo: Codigo, trecho: Trecho, mensagem: String) -> Self {
+        Self {
+            severidade: Severidade::Aviso,
+            ..Self::erro(codigo, trecho, mensagem)
+        }
+    }
+
+    /// adiciona nota explicativa ao diagnostico
+    pub fn com_nota(mut self, nota: String) -> Self {
+        self.notas.push(nota);
+        self
+    }
+
+    /// adiciona sugestao de correcao ao diagnostico
+    pub fn com_sugestao(mut self, sugestao: String) -> Self {
+        self.sugestoes.push(sugestao);
+        self
+    }
+
+    /// verifica se o diagnostico impede a compilacao
+    pub fn is_erro(&self) -> bool {
+        self.severidade == Severidade::Erro
+    }
+
+    /// retorna linha do diagnostico
+    pub fn linha(&self) -> u32 {
+        self.trecho.linha
+    }
+
+    /// retorna texto do diagnostico no formato esperado pelo corretor, `Linha N: mensagem`, ou `Linha N: aviso: mensagem`
+    pub fn renderizar(&self) -> String {
+        match self.severidade {
+            Severidade::Erro => format!("Linha {}: {}\n", self.trecho.linha, self.mensagem),
+            Severidade::Aviso => format!("Linha {}: aviso: {}\n", self.trecho.linha, self.mensagem),
+        }
+    }
+
+    /// retorna texto completo do diagnostico, para leitura por pessoas, com a coluna, quando conhecida, e
+    /// as notas e sugestoes nas linhas seguintes
+    pub fn renderizar_completo(&self) -> String {
+        let posicao = match self.trecho.coluna {
+            0 => format!("Linha {}", self.trecho.linha),
+            coluna => format!("Linha {}, coluna {}", self.trecho.linha, coluna),
+        };
+        let mut texto = match self.severidade {
+            Severidade::Erro => format!("{}: {}\n", posicao, self.mensagem),
+            Severidade::Aviso => format!("{}: aviso: {}\n", posicao, self.mensagem),
+        };
+        for nota in &self.notas {
+            texto += &format!("    nota: {}\n", nota);
+        }
+        for sugestao in &self.sugestoes {
+            texto += &format!("    sugestao: {}\n", sugestao);
+        }
+        texto
+    }
+}
+
+/// escreve `avisos` e `erros` completos na saida de erro, e retorna apenas os erros no formato do corretor
+pub fn relatar(avisos: &[Diagnostico], erros: &[Diagnostico]) -> String {
+    for diagnostico in avisos.iter().chain(erros) {
+        eprint!("{}", diagnostico.renderizar_completo());
+    }
+    erros.iter().map(Diagnostico::renderizar).collect()
+}
@@ -1,9 +1,14 @@
+use std::collections::HashMap;
+
 use crate::semantico::{
+    biblioteca::{self, Embutida},
     escopos::Escopos,
     tabela_de_simbolos::TipoSimbolo,
+    tipos::{TabelaDeTipos, TipoId},
     visitor::Visitor,
 };
 
+use crate::lexico::token::TipoToken;
 use crate::sintatico::arvore_sintatica::{NoAST, RegraAST};
 
 
@@ -12,16 +17,38 @@ pub struct Gerador {
     escopos: Escopos,
     saida: String,
     identacao: u8,
+    embutidas: Vec<&'static Embutida>,
+    inicializacoes: String,
+    // parametros 'var' da rotina atual, recebidos como ponteiros para as variaveis passadas na chamada
+    referencias: Vec<String>,
+    // tipo em C de cada registro ja gerado
+    rotulos: HashMap<TipoId, String>,
+    // registros cujas funcoes de comparacao ja foram geradas
+    igualdades: Vec<TipoId>,
+    // estruturas e funcoes de comparacao emitidas antes da rotina atual
+    definicoes: String,
+    // main tambem conta como rotina, ja que suas declaracoes ficam dentro dela em C
+    em_rotina: bool,
+    // registro movido para fora de uma rotina, cujos campos nao podem usar os tipos declarados nela
+    movendo: Option<TipoId>,
 }
 
 impl Gerador {
 
-    /// retorna instancia de gerador de codigo em C
-    pub fn new() -> Self {
+    /// retorna instancia de gerador de codigo em C, que usa os tipos `tipos` registrados pela analise semantica
+    pub fn new(tipos: TabelaDeTipos) -> Self {
         Self {
-            escopos: Escopos::new(TipoSimbolo::Vazio),
+            escopos: Escopos::com_tipos(tipos),
             saida: "".to_string(),
             identacao: 0,
+            embutidas: vec![],
+            inicializacoes: "".to_string(),
+            referencias: vec![],
+            rotulos: HashMap::new(),
+            igualdades: vec![],
+            definicoes: "".to_string(),
+            em_rotina: false,
+            movendo: None,
         }
     }
 
@@ -30,6 +57,254 @@ impl Gerador {
         &self.saida
     }
 
+    /// retorna cabecalhos e definicoes em C das funcoes embutidas utilizadas pelo programa
+    fn biblioteca(&self) -> String {
+        let mut cabecalhos: Vec<&str> = vec![];
+        for embutida in &self.embutidas {
+            for cabecalho in embutida.cabecalhos {
+                if !cabecalhos.contains(cabecalho) {
+                    cabecalhos.push(cabecalho);
+                }
+            }
+        }
+
+        let mut codigo = "".to_string();
+        for cabecalho in cabecalhos {
+            codigo += &format!("\n#include <{}>", cabecalho);
+        }
+        if self.embutidas.iter().any(|embutida| embutida.retorna_literal()) {
+            codigo += "\n";
+            codigo += biblioteca::BUFFER_LITERAL;
+        }
+        for embutida in &self.embutidas {
+            if !embutida.definicao.is_empty() {
+                codigo += "\n";
+                codigo += embutida.definicao;
+            }
+        }
+        codigo
+    }
+
+    /// registra uso de uma funcao embutida, para que sua definicao seja incluida no programa
+    fn usar(&mut self, embutida: &'static Embutida) {
+        if !self.embutidas.iter().any(|usada| std::ptr::eq(*usada, embutida)) {
+            self.embutidas.push(embutida);
+        }
+    }
+
+    /// retorna especificador de formato de scanf e printf para valores do tipo `tipo`
+    fn formato(tipo: &TipoSimbolo) -> &'static str {
+        match tipo {
+            TipoSimbolo::Cadeia => "s",
+            TipoSimbolo::Inteiro | TipoSimbolo::Enumeracao(_) => "d",
+            TipoSimbolo::Real => "f",
+            TipoSimbolo::Caractere => "c",
+            _ => ""
+        }
+    }
+
+    /// retorna formato de scanf para valores do tipo `tipo`\
+    /// caracteres ignoram espacos e quebras de linha anteriores, como fazem os demais formatos
+    fn formato_leitura(tipo: &TipoSimbolo) -> String {
+        match tipo {
+            TipoSimbolo::Caractere => " %c".to_string(),
+            _ => format!("%{}", Gerador::formato(tipo)),
+        }
+    }
+
+    /// retorna tipo do valor lido em `identificador`, que eh o tipo apontado caso seja precedido por '^'
+    fn tipo_lido(circunflexo: &NoAST, identificador: &NoAST, escopos: &Escopos) -> TipoSimbolo {
+        match (circunflexo.regra(), identificador.tipo(escopos)) {
+            (RegraAST::Circunflexo, TipoSimbolo::Ponteiro(tipo)) => escopos.tipos().resolver(*tipo),
+            (_, tipo) => tipo,
+        }
+    }
+
+    /// gera chamada a procedimento embutido de manipulacao de arquivos
+    fn chamada_embutida(&mut self, embutida: &'static Embutida, argumentos: &[NoAST]) {
+        self.usar(embutida);
+        match embutida.nome {
+            // abra(arq, nome, modo) atribui o arquivo aberto a arq
+            "abra" => {
+                self.visit(&argumentos[0]);
+                self.saida += &format!(" = {}(", embutida.nome_c);
+                self.visit(&argumentos[1]);
+                self.saida += ", ";
+                self.visit(&argumentos[2]);
+                self.saida += ");";
+            }
+            // leia_arquivo(arq, x, y) le cada variavel com seu proprio formato, assim como leia
+            "leia_arquivo" => {
+                for (i, argumento) in argumentos[1..].iter().enumerate() {
+                    if i > 0 { self.new_line() }
+                    let tipo = argumento.tipo(&self.escopos);
+                    self.saida += &format!("{}(", embutida.nome_c);
+                    self.visit(&argumentos[0]);
+                    self.saida += &format!(", \"{}\", ", Gerador::formato_leitura(&tipo));
+                    if tipo != TipoSimbolo::Cadeia {
+                        self.saida += "&";
+                    }
+                    self.visit(argumento);
+                    self.saida += ");";
+                }
+            }
+            // escreva_arquivo(arq, a, b) segue o formato de escreva
+            "escreva_arquivo" => {
+                self.saida += &format!("{}(", embutida.nome_c);
+                self.visit(&argumentos[0]);
+                self.saida += ", \"";
+                for argumento in &argumentos[1..] {
+                    self.saida += "%";
+                    self.saida += Gerador::formato(&argumento.tipo(&self.escopos));
+                }
+                self.saida += "\"";
+                for argumento in &argumentos[1..] {
+                    self.saida += ", ";
+                    self.visit(argumento);
+                }
+                self.saida += ");";
+            }
+            _ => {
+                self.saida += &format!("{}(", embutida.nome_c);
+                for (i, argumento) in argumentos.iter().enumerate() {
+                    if i > 0 { self.saida += ", " }
+                    self.visit(argumento);
+                }
+                self.saida += ");";
+            }
+        }
+    }
+
+    /// gera inicializador C para o valor inicial de uma variavel
+    fn valor_inicial(&mut self, valor: &NoAST) {
+        if let RegraAST::ValoresIniciais = valor.regra() {
+            // valores sao retornados em ordem inversa
+            let valores: Vec<NoAST> = valor.variaveis().into_iter().rev().collect();
+            self.saida += "{";
+            for (i, valor) in valores.iter().enumerate() {
+                if i > 0 { self.saida += ", " }
+                self.visit(valor);
+            }
+            self.saida += "}";
+        } else {
+            self.visit(valor);
+        }
+    }
+
+    /// gera atribuicoes dos valores iniciais de cada identificador declarado por `variavel`
+    fn atribuicoes_iniciais(&mut self, variavel: &NoAST, tipo: &TipoSimbolo) {
+        let valor = &variavel.filhos()[3].filhos()[0];
+        let valores: Vec<NoAST> = if let RegraAST::ValoresIniciais = valor.regra() {
+            valor.variaveis().into_iter().rev().collect()
+        } else { vec![] };
+
+        for identificador in variavel.identificadores() {
+            let nome = identificador.filhos()[0].texto() + &identificador.filhos()[1].texto();
+            let destinos: Vec<(String, &NoAST)> = if valores.is_empty() {
+                vec![(nome, valor)]
+            } else {
+                valores.iter().enumerate().map(|(i, valor)| (format!("{}[{}]", nome, i), valor)).collect()
+            };
+
+            for (destino, valor) in destinos {
+                self.new_line();
+                if *tipo == TipoSimbolo::Cadeia {
+                    self.saida += &format!("strcpy({}, ", destino);
+                    self.visit(valor);
+                    self.saida += ");";
+                } else {
+                    self.saida += &format!("{} = ", destino);
+                    self.visit(valor);
+                    self.saida += ";";
+                }
+            }
+        }
+    }
+
+    /// retorna o codigo gerado para `no`, sem adiciona-lo a saida
+    fn codigo(&mut self, no: &NoAST) -> String {
+        let saida = std::mem::take(&mut self.saida);
+        self.visit(no);
+        std::mem::replace(&mut self.saida, saida)
+    }
+
+    /// retorna tipo em C correspondente a `tipo`, sem usar os nomes de tipos declarados em rotinas
+    fn tipo_c(&self, tipo: &TipoSimbolo) -> String {
+        match self.escopos.tipos().resolver(tipo.clone()) {
+            TipoSimbolo::Real => "float".to_string(),
+            TipoSimbolo::Cadeia | TipoSimbolo::Caractere => "char".to_string(),
+            TipoSimbolo::Arquivo => "FILE".to_string(),
+            // registros apontados antes de serem gerados so podem ser locais quando o registro atual eh movido
+            TipoSimbolo::Registro(id) => self.rotulos.get(&id).cloned().unwrap_or_else(|| match self.movendo {
+                Some(_) => format!("struct la_registro{}", id),
+                None => self.escopos.tipos().registro(id).nome.clone(),
+            }),
+            TipoSimbolo::Ponteiro(apontado) => format!("{}*", self.tipo_c(&apontado)),
+            _ => "int".to_string(),
+        }
+    }
+
+    /// retorna nome da funcao que compara campo a campo registros do tipo `id`, ja que o C nao compara estruturas\
+    /// a funcao eh definida antes da rotina atual na primeira vez em que eh usada
+    fn funcao_igualdade(&mut self, id: TipoId) -> String {
+        let tipo = self.tipo_c(&TipoSimbolo::Registro(id));
+        let nome = format!("igual_{}", tipo.trim_start_matches("struct "));
+        if self.igualdades.contains(&id) {
+            return nome
+        }
+        self.igualdades.push(id);
+
+        let mut corpo = "".to_string();
+        for campo in self.escopos.tipos().registro(id).campos.clone() {
+            let (mut a, mut b) = (format!("a->{}", campo.nome), format!("b->{}", campo.nome));
+            // vetores sao comparados elemento a elemento, ja que literais e registros nao podem ser comparados byte a byte
+            let mut lacos = "".to_string();
+            for dimensao in 0..campo.tamanhos.len() {
+                lacos += &format!("for (int i{d} = 0; i{d} < (int) (sizeof({a}) / sizeof({a}[0])); i{d}++) ", d = dimensao, a = a);
+                a += &format!("[i{}]", dimensao);
+                b += &format!("[i{}]", dimensao);
+            }
+            let diferentes = match self.escopos.tipos().resolver(campo.tipo.clone()) {
+                TipoSimbolo::Cadeia => format!("strcmp({}, {}) != 0", a, b),
+                TipoSimbolo::Registro(campo_id) => format!("!{}(&{}, &{})", self.funcao_igualdade(campo_id), a, b),
+                _ => format!("{} != {}", a, b),
+            };
+            corpo += &format!("\n\t{}if ({}) return 0;", lacos, diferentes);
+        }
+        self.definicoes += &format!("\n\nstatic int {}(const {t} *a, const {t} *b) {{{}\n\treturn 1;\n}}", nome, corpo, t = tipo);
+        nome
+    }
+
+    /// verifica se `identificador`, declarado por `parametro`, eh recebido como ponteiro\
+    /// literais e vetores ja sao passados por endereco em C, entao so os demais parametros 'var' precisam de ponteiro
+    fn por_referencia(parametro: &NoAST, identificador: &NoAST, escopos: &Escopos) -> bool {
+        *parametro.filhos()[0].regra() == RegraAST::Var
+            && identificador.dimensoes().is_empty()
+            && escopos.tipos().resolver(parametro.tipo(escopos)) != TipoSimbolo::Cadeia
+    }
+
+    /// gera argumentos de uma chamada, passando o endereco das variaveis recebidas por parametros 'var'
+    fn argumentos(&mut self, chamada: &NoAST) {
+        // argumentos sao retornados em ordem inversa
+        let mut argumentos = chamada.variaveis();
+        argumentos.reverse();
+        // como em `por_referencia`, literais e vetores ja sao passados por endereco
+        let tipos = self.escopos.tipos();
+        let referencias: Vec<bool> = match self.escopos.verificar(&chamada.filhos()[0].texto()).map(|simbolo| simbolo.tipo()) {
+            Some(TipoSimbolo::Procedimento(parametros))
+            | Some(TipoSimbolo::Funcao { parametros, retorno: _ }) => parametros.iter()
+                .map(|parametro| parametro.referencia && parametro.tamanhos.is_empty() && tipos.resolver(parametro.tipo.clone()) != TipoSimbolo::Cadeia)
+                .collect(),
+            _ => vec![]
+        };
+
+        for (i, argumento) in argumentos.iter().enumerate() {
+            if i > 0 { self.saida += ", " }
+            if referencias.get(i) == Some(&true) { self.saida += "&" }
+            self.visit(argumento);
+        }
+    }
+
     /// adiciona nova linha na saida e o numero correto de espacamentos para identacao
     fn new_line(&mut self) {
         self.saida += "\n";
@@ -44,34 +319,54 @@ impl Visitor for Gerador {
     /// gera trecho de codigo em linguagem C referente a cada no da arvore sintatica
     fn visit(&mut self, no: &NoAST) {
         let filhos = no.filhos();
-        let escopos = self.escopos.clone();
 
         match no.regra() {
 
-            // programa : declaracoes 'algoritmo' corpo 'fim_algoritmo'
+            // programa : declaracoes 'algoritmo' corpo 'fim_algoritmo' fecha_escopo
             RegraAST::Programa => {
-                self.saida += "#include <stdio.h>\n#include <stdlib.h>";
+                self.saida += "#include <stdio.h>\n#include <stdlib.h>\n#include <string.h>";
+                let inicio = self.saida.len();
                 self.visit(&filhos[0]);
+                let principal = self.saida.len();
+                self.em_rotina = true;
                 self.saida += "\n\nint main(void) {";
+                self.saida += &self.inicializacoes;
                 self.identacao += 1;
                 self.visit(&filhos[1]);
                 self.new_line();
                 self.saida += "return 0;\n}\n";
                 self.identacao -= 1;
-                println!("{}", self.identacao);
+                self.em_rotina = false;
+                let definicoes = std::mem::take(&mut self.definicoes);
+                self.saida.insert_str(principal, &definicoes);
+
+                // funcoes embutidas so sao conhecidas apos percorrer todo o programa
+                let biblioteca = self.biblioteca();
+                self.saida.insert_str(inicio, &biblioteca);
             }
             
             // declaracao_local :
             //     'declare' variavel
             //     | 'tipo' IDENT ':' tipo
-            RegraAST::DeclaracaoTipo => {
+            RegraAST::DeclaracaoTipo(_) => {
                 self.new_line();
                 self.saida += "typedef ";
+                let tipo = no.tipo(&self.escopos);
+                // copias de registros sao apelidos em C, e compartilham o tipo do registro original
+                if let (TipoSimbolo::Registro(id), TipoSimbolo::Registro(original)) = (&tipo, filhos[1].tipo(&self.escopos)) {
+                    if let Some(rotulo) = self.rotulos.get(&original).cloned() {
+                        self.rotulos.insert(*id, rotulo);
+                    }
+                }
                 self.visit(&filhos[1]);
+                // registros e enumeracoes ja terminam em espaco, mas apelidos precisam separar o tipo base do nome
+                if !self.saida.ends_with(' ') {
+                    self.saida += " ";
+                }
                 self.visit(&filhos[0]);
                 self.saida += ";";
                 let escopo_atual = self.escopos.escopo_atual();
-                escopo_atual.inserir(&filhos[0].texto(), &filhos[1].tipo(&escopos));
+                escopo_atual.inserir(&filhos[0].texto(), &tipo);
             }
 
             //     | 'constante' IDENT ':' tipo_basico '=' valor_constante
@@ -80,63 +375,85 @@ impl Visitor for Gerador {
                 self.new_line();
                 self.saida += &format!("#define {} ", filhos[0].texto());
                 self.visit(&filhos[2]);
+                let tipo = filhos[1].tipo(&self.escopos);
+                self.escopos.escopo_atual().inserir(&filhos[0].texto(), &tipo);
             }
 
-            // valor_constante : CADEIA | NUM_INT | NUM_REAL | 'verdadeiro' | 'falso'
+            // valor_constante : CADEIA | CARACTERE | NUM_INT | NUM_REAL | 'verdadeiro' | 'falso'
+            // valores logicos sao inteiros em C, ja que stdbool.h nao eh incluido
             RegraAST::ValorConstante (token) => {
                 self.saida += &match token.lexema().as_ref() {
-                    "verdadeiro" => "true".to_string(),
-                    "falso" => "false".to_string(),
+                    "verdadeiro" => "1".to_string(),
+                    "falso" => "0".to_string(),
                     _ => token.lexema()
                 }
             }
 
-            // variavel : identificador identificadores ':' tipo
+            // variavel : identificador identificadores ':' tipo inicializacao
             RegraAST::Variavel => {
                 self.new_line();
-                let mut tipo = filhos[2].tipo(&escopos);
+                let tipo_declarado = filhos[2].tipo(&self.escopos);
+                let mut tipo = self.escopos.tipos().resolver(tipo_declarado.clone());
 
                 let ponteiro = if let TipoSimbolo::Ponteiro(tipo_point) = tipo {
                     tipo = *tipo_point;
                     true
-                } else { false };
-
-                self.visit(&filhos[2]);
-                self.saida += " ";
-
-                if ponteiro { self.saida += "*" }
-                self.visit(&filhos[0]);
-                if tipo == TipoSimbolo::Cadeia {
-                    self.saida += "[80]"
+                } else { tipo == TipoSimbolo::Arquivo };
+
+                // fora de rotinas o C so aceita inicializadores constantes, e literais sao copiados com strcpy,
+                // entao nesses casos os valores iniciais viram atribuicoes
+                let inicializacao = &filhos[3];
+                let global = self.identacao == 0;
+                let atribuido = *inicializacao.regra() != RegraAST::Vazio && (global || tipo == TipoSimbolo::Cadeia);
+
+                let movido = self.movendo.filter(|_| !matches!(filhos[2].regra(), RegraAST::Registro(_)));
+                if let Some(registro) = movido {
+                    // o tipo do campo ja foi resolvido pela analise semantica, inclusive quando aponta para registros adiante
+                    let nome = no.identificadores()[0].filhos()[0].texto();
+                    let campos = &self.escopos.tipos().registro(registro).campos;
+                    let tipo_campo = campos.iter().find(|campo| campo.nome == nome).map_or(tipo.clone(), |campo| campo.tipo.clone());
+                    self.saida += &match tipo_campo {
+                        TipoSimbolo::Ponteiro(apontado) => self.tipo_c(&apontado),
+                        tipo_campo => self.tipo_c(&tipo_campo),
+                    };
+                } else {
+                    self.visit(&filhos[2]);
                 }
+                self.saida += " ";
 
-                let mut identificadores = &filhos[1];
-                while *identificadores.regra() != RegraAST::Vazio {
-                    let identificador = &identificadores.filhos()[0];
-                    self.saida += ", ";
+                for (i, identificador) in no.identificadores().into_iter().enumerate() {
+                    if i > 0 { self.saida += ", " }
                     if ponteiro { self.saida += "*" }
-                    self.visit(&identificador);
+                    self.visit(identificador);
                     if tipo == TipoSimbolo::Cadeia {
                         self.saida += "[80]"
                     }
-                    identificadores = &identificadores.filhos()[1]
+                    if *inicializacao.regra() != RegraAST::Vazio && !atribuido {
+                        self.saida += " = ";
+                        self.valor_inicial(&inicializacao.filhos()[0]);
+                    }
                 }
                 self.saida += ";";
+
+                if atribuido && global {
+                    // atribuicoes sao emitidas no inicio de main
+                    let saida = std::mem::take(&mut self.saida);
+                    self.identacao += 1;
+                    self.atribuicoes_iniciais(no, &tipo);
+                    self.identacao -= 1;
+                    self.inicializacoes += &std::mem::replace(&mut self.saida, saida);
+                } else if atribuido {
+                    self.atribuicoes_iniciais(no, &tipo);
+                }
                 
+                let campos = self.escopos.tipos().campos(&tipo_declarado);
                 let escopo_atual = self.escopos.escopo_atual();
-                for ident in no.idents() {
-                    let nome = ident.lexema();
-                    escopo_atual.inserir(&nome, &tipo);
-
-                    if let TipoSimbolo::Registro(ref atributos) = tipo {
-                        for atributo in atributos {
-                            let tipo = atributo.tipo(&escopos);
-                            let idents = atributo.idents();
-                            for ident in idents {
-                                let nome = format!("{}.{}", nome, ident.lexema());
-                                escopo_atual.inserir(&nome, &tipo)
-                            }
-                        }
+                for identificador in no.identificadores() {
+                    let nome = identificador.filhos()[0].texto();
+                    escopo_atual.inserir_vetor(&nome, &tipo_declarado, identificador.dimensoes().len());
+
+                    for campo in &campos {
+                        escopo_atual.inserir_vetor(&format!("{}.{}", nome, campo.nome), &campo.tipo, campo.tamanhos.len());
                     }
                 }
             }
@@ -144,11 +461,23 @@ impl Visitor for Gerador {
 
             // identificador : IDENT identificador2 dimensao
             RegraAST::Identificador => {
-                self.saida += &no.texto()
+                // campos acessados atraves de ponteiros usam o operador '->'
+                let mut texto = no.texto().replace("^.", "->");
+                // parametros 'var' sao desreferenciados a cada uso
+                let nome = filhos[0].texto();
+                if self.referencias.contains(&nome) {
+                    texto = format!("(*{}){}", nome, &texto[nome.len()..]);
+                }
+                self.saida += &texto
             }
 
             // tipo_estendido : circunflexo tipo_basico_ident
             RegraAST::TipoExtendido => {
+                // ponteiros para registros ainda nao declarados os referenciam pelo rotulo
+                let adiante = matches!(filhos[1].regra(), RegraAST::Ident(_)) && !self.escopos.existe(&filhos[1].texto());
+                if *filhos[0].regra() == RegraAST::Circunflexo && adiante {
+                    self.saida += "struct ";
+                }
                 self.visit(&filhos[1]);
             }
             // tipo_basico_ident : tipo_basico
@@ -157,12 +486,15 @@ impl Visitor for Gerador {
                 self.saida += &token.lexema();
             }
 
-            // tipo_basico : 'literal' | 'inteiro' | 'real' | 'logico'
+            // tipo_basico : 'literal' | 'inteiro' | 'real' | 'logico' | 'arquivo' | 'caractere'
             RegraAST::TipoBasico (token) => {
                 self.saida += match token.lexema().as_ref() {
                     "literal" => "char",
                     "inteiro" => "int",
                     "real" => "float",
+                    "arquivo" => "FILE",
+                    "caractere" => "char",
+                    "logico" => "int",
                     _ => ""
                 }
             }
@@ -173,27 +505,71 @@ impl Visitor for Gerador {
             }
 
             // registro : 'registro' variaveis 'fim_Registro' fecha_escopo
-            RegraAST::Registro => {
-                self.saida += "struct {";
+            RegraAST::Registro(_) => {
+                let TipoSimbolo::Registro(id) = no.tipo(&self.escopos) else { return };
+                // registros recebem um rotulo, para que seus campos possam apontar para eles:
+                // o proprio nome, para registros nomeados globais, ou um rotulo unico para os demais
+                let nome = self.escopos.tipos().registro(id).nome.clone();
+                let rotulo = if nome.is_empty() || self.em_rotina { format!("la_registro{}", id) } else { nome.clone() };
+                self.rotulos.insert(id, if rotulo == nome { nome } else { format!("struct {}", rotulo) });
+
+                // registros declarados em rotinas sao definidos antes delas, onde suas funcoes de comparacao os conhecem
+                let movido = self.em_rotina;
+                let (identacao, movendo) = (self.identacao, self.movendo);
+                let saida = if movido { std::mem::take(&mut self.saida) } else { "".to_string() };
+                if movido {
+                    (self.identacao, self.em_rotina) = (0, false);
+                    self.new_line();
+                    self.new_line();
+                }
+                if movido || movendo.is_some() {
+                    self.movendo = Some(id);
+                }
+
+                self.saida += &format!("struct {} {{", rotulo);
                 self.identacao += 1;
                 self.visit(&filhos[0]);
                 self.identacao -= 1;
                 self.new_line();
                 self.saida += "} ";
+                self.movendo = movendo;
+
+                if movido {
+                    self.definicoes += self.saida.trim_end();
+                    self.definicoes += ";";
+                    self.saida = saida;
+                    (self.identacao, self.em_rotina) = (identacao, true);
+                    self.saida += &format!("struct {}", rotulo);
+                }
+            }
+
+            // enumeracao : '(' IDENT valores_enumeracao ')'
+            RegraAST::Enumeracao(_) => {
+                self.saida += &format!("enum {{ {} }} ", no.valores().join(", "));
+
+                let tipo = no.tipo(&self.escopos);
+                let escopo_atual = self.escopos.escopo_atual();
+                for valor in no.valores() {
+                    escopo_atual.inserir(&valor, &tipo);
+                }
             }
 
             // declaracao_global :
             //     'procedimento' IDENT '(' parametros ')' declaracoes_locais cmds 'fim_procedimento' fecha_escopo
             RegraAST::DeclaracaoProcedimento => {
+                let inicio = self.saida.len();
+                self.em_rotina = true;
                 self.new_line();
                 self.new_line();
                 let nome = filhos[0].texto();
                 self.saida += &format!("void {}(", nome);
+                self.referencias.clear();
                 self.visit(&filhos[1]);
                 self.saida += ") {";
                 self.identacao += 1;
 
-                self.escopos.escopo_atual().inserir(&nome, &no.tipo(&escopos));
+                let tipo = no.tipo(&self.escopos);
+                self.escopos.escopo_atual().inserir(&nome, &tipo);
                 self.escopos.novo_escopo(TipoSimbolo::Vazio);
 
                 self.visit(&filhos[2]);
@@ -202,23 +578,31 @@ impl Visitor for Gerador {
                 self.identacao -= 1;
                 self.new_line();
                 self.saida += "}";
+                self.referencias.clear();
                 self.visit(&filhos[4]);
+                self.em_rotina = false;
+                let definicoes = std::mem::take(&mut self.definicoes);
+                self.saida.insert_str(inicio, &definicoes);
             }
 
             //     | 'funcao' IDENT '(' parametros ')' ':' tipo_estendido declaracoes_locais cmds 'fim_funcao' fecha_escopo
             RegraAST::DeclaracaoFuncao => {
+                let inicio = self.saida.len();
+                self.em_rotina = true;
                 self.new_line();
                 self.new_line();
                 let nome = filhos[0].texto();
 
                 self.visit(&filhos[2]);
                 self.saida += &format!(" {}(", nome);
+                self.referencias.clear();
                 self.visit(&filhos[1]);
                 self.saida += ") {";
                 self.identacao += 1;
 
-                self.escopos.escopo_atual().inserir(&nome, &no.tipo(&escopos));
-                self.escopos.novo_escopo(filhos[2].tipo(&escopos));
+                let tipo = no.tipo(&self.escopos);
+                self.escopos.escopo_atual().inserir(&nome, &tipo);
+                self.escopos.novo_escopo(filhos[2].tipo(&self.escopos));
 
                 self.visit(&filhos[3]);
                 self.visit(&filhos[4]);
@@ -226,30 +610,29 @@ impl Visitor for Gerador {
                 self.identacao -= 1;
                 self.new_line();
                 self.saida += "}";
+                self.referencias.clear();
                 self.visit(&filhos[5]);
+                self.em_rotina = false;
+                let definicoes = std::mem::take(&mut self.definicoes);
+                self.saida.insert_str(inicio, &definicoes);
             }
 
             // parametro : var identificador identificadores ':' tipo_estendido
             RegraAST::Parametro => {
-                let tipo = filhos[3].tipo(&escopos);
+                let tipo = self.escopos.tipos().resolver(filhos[3].tipo(&self.escopos));
 
-                self.visit(&filhos[3]);
-                self.saida += " ";
-
-                if tipo == TipoSimbolo::Cadeia { self.saida += "*" }
-                self.visit(&filhos[1]);
-                self.escopos.escopo_atual().inserir(&filhos[1].texto(), &tipo);
-
-                let mut identificadores = &filhos[2];
-                while *identificadores.regra() != RegraAST::Vazio {
-                    let identificador = &identificadores.filhos()[0];
-                    self.saida += ", ";
+                for (i, identificador) in no.identificadores().into_iter().enumerate() {
+                    if i > 0 { self.saida += ", " }
                     self.visit(&filhos[3]);
                     self.saida += " ";
-                    if tipo == TipoSimbolo::Cadeia { self.saida += "*" }
-                    self.visit(&identificador);
+                    if tipo == TipoSimbolo::Cadeia || tipo == TipoSimbolo::Arquivo { self.saida += "*" }
+                    let referencia = Gerador::por_referencia(no, identificador, &self.escopos);
+                    if referencia { self.saida += "*" }
+                    self.visit(identificador);
                     self.escopos.escopo_atual().inserir(&identificador.texto(), &tipo);
-                    identificadores = &identificadores.filhos()[1];
+                    if referencia {
+                        self.referencias.push(identificador.filhos()[0].texto());
+                    }
                 }
             }
 
@@ -263,15 +646,12 @@ impl Visitor for Gerador {
             // cmdLeia : 'leia' '(' circunflexo identificador cmdLeia2 ')'
             RegraAST::CMDLeia => {
                 self.new_line();
-                self.saida += "scanf(\"%";
-                self.saida += match filhos[1].tipo(&escopos) {
-                    TipoSimbolo::Cadeia => "s",
-                    TipoSimbolo::Inteiro => "d",
-                    TipoSimbolo::Real => "f",
-                    _ => ""
-                };
+                self.saida += "scanf(\"";
+                let tipo = Gerador::tipo_lido(&filhos[0], &filhos[1], &self.escopos);
+                self.saida += &Gerador::formato_leitura(&tipo);
                 self.saida += "\", ";
-                if filhos[1].tipo(&escopos) != TipoSimbolo::Cadeia {
+                // um ponteiro ja eh o endereco onde o valor deve ser lido
+                if tipo != TipoSimbolo::Cadeia && *filhos[0].regra() != RegraAST::Circunflexo {
                     self.saida += "&";
                 }
                 self.visit(&filhos[1]);
@@ -282,15 +662,12 @@ impl Visitor for Gerador {
             // cmdLeia2 : ',' circunflexo identificador cmdLeia2 | <<vazio>>
             RegraAST::CMDLeia2 => {
                 self.new_line();
-                self.saida += "scanf(\"%";
-                self.saida += match filhos[1].tipo(&escopos) {
-                    TipoSimbolo::Cadeia => "s",
-                    TipoSimbolo::Inteiro => "d",
-                    TipoSimbolo::Real => "f",
-                    _ => ""
-                };
+                self.saida += "scanf(\"";
+                let tipo = Gerador::tipo_lido(&filhos[0], &filhos[1], &self.escopos);
+                self.saida += &Gerador::formato_leitura(&tipo);
                 self.saida += "\", ";
-                if filhos[1].tipo(&escopos) != TipoSimbolo::Cadeia {
+                // um ponteiro ja eh o endereco onde o valor deve ser lido
+                if tipo != TipoSimbolo::Cadeia && *filhos[0].regra() != RegraAST::Circunflexo {
                     self.saida += "&";
                 }
                 self.visit(&filhos[1]);
@@ -299,34 +676,47 @@ impl Visitor for Gerador {
                 self.visit(&filhos[2]);
             }
 
-            // cmdEscreva : 'escreva' '(' expressao expressoes ')'
+            // cmdEscreva : 'escreva' '(' escrita escritas ')'
             RegraAST::CMDEscreva => {
                 self.new_line();
-                self.saida += "printf(\"%";
-                self.saida += match filhos[0].tipo(&escopos) {
-                    TipoSimbolo::Cadeia => "s",
-                    TipoSimbolo::Inteiro => "d",
-                    TipoSimbolo::Real => "f",
-                    _ => ""
-                };
-                let mut expressoes = &filhos[1];
-                while *expressoes.regra() != RegraAST::Vazio {
+                let mut escritas = vec![&filhos[0]];
+                let mut resto = &filhos[1];
+                while *resto.regra() != RegraAST::Vazio {
+                    escritas.push(&resto.filhos()[0]);
+                    resto = &resto.filhos()[1];
+                }
+
+                // largura e precisao constantes vao no formato, as demais sao passadas como argumento com '*'
+                let mut argumentos = vec![];
+                self.saida += "printf(\"";
+                for escrita in escritas {
+                    let expressao = &escrita.filhos()[0];
                     self.saida += "%";
-                    self.saida += match expressoes.filhos()[0].tipo(&escopos) {
-                        TipoSimbolo::Cadeia => "s",
-                        TipoSimbolo::Inteiro => "d",
-                        TipoSimbolo::Real => "f",
-                        _ => ""
-                    };
-                    expressoes = &expressoes.filhos()[1];
+                    for (i, campo) in escrita.filhos()[1].filhos().iter().enumerate() {
+                        if *campo.regra() == RegraAST::Vazio {
+                            continue
+                        }
+                        if i > 0 { self.saida += "." }
+                        match campo.texto().parse::<u32>() {
+                            Ok(valor) => self.saida += &valor.to_string(),
+                            Err(_) => {
+                                self.saida += "*";
+                                argumentos.push(campo);
+                            }
+                        }
+                    }
+                    self.saida += Gerador::formato(&expressao.tipo(&self.escopos));
+                    argumentos.push(expressao);
+                }
+                self.saida += "\"";
+                for argumento in argumentos {
+                    self.saida += ", ";
+                    self.visit(argumento);
                 }
-                self.saida += "\", ";
-                self.visit(&filhos[0]);
-                self.visit(&filhos[1]);
                 self.saida += ");";
             }
 
-            // cmdSe : 'se' expressao 'entao' cmds senao 'fim_se'
+            // cmdSe : 'se' expressao 'entao' cmds senao_se 'fim_se'
             RegraAST::CMDSe => {
                 self.new_line();
                 self.saida += "if (";
@@ -337,13 +727,41 @@ impl Visitor for Gerador {
                 self.identacao -= 1;
                 self.new_line();
                 self.saida += "}";
-                if *filhos[2].regra() != RegraAST::Vazio {
-                    self.saida += " else {";
-                    self.identacao += 1;
-                    self.visit(&filhos[2]);
-                    self.identacao -= 1;
-                    self.new_line();
-                    self.saida += "}";
+                match filhos[2].regra() {
+                    RegraAST::Vazio => {}
+                    RegraAST::SenaoSe => self.visit(&filhos[2]),
+                    _ => {
+                        self.saida += " else {";
+                        self.identacao += 1;
+                        self.visit(&filhos[2]);
+                        self.identacao -= 1;
+                        self.new_line();
+                        self.saida += "}";
+                    }
+                }
+            }
+
+            // senao_se : 'senao' 'se' expressao 'entao' cmds senao_se | senao
+            RegraAST::SenaoSe => {
+                self.saida += " else if (";
+                self.visit(&filhos[0]);
+                self.saida += ") {";
+                self.identacao += 1;
+                self.visit(&filhos[1]);
+                self.identacao -= 1;
+                self.new_line();
+                self.saida += "}";
+                match filhos[2].regra() {
+                    RegraAST::Vazio => {}
+                    RegraAST::SenaoSe => self.visit(&filhos[2]),
+                    _ => {
+                        self.saida += " else {";
+                        self.identacao += 1;
+                        self.visit(&filhos[2]);
+                        self.identacao -= 1;
+                        self.new_line();
+                        self.saida += "}";
+                    }
                 }
             }
 
@@ -360,8 +778,9 @@ impl Visitor for Gerador {
                     self.saida += "default:";
                     self.identacao += 1;
                     self.visit(&filhos[2]);
+                    self.identacao -= 1;
                 }
-                self.identacao -= 2;
+                self.identacao -= 1;
                 self.new_line();
                 self.saida += "}";
             }
@@ -369,11 +788,15 @@ impl Visitor for Gerador {
             // cmdPara : 'para' IDENT '<-' exp_aritmetica 'ate' exp_aritmetica 'faca' cmds 'fim_para'
             RegraAST::CMDPara => {
                 self.new_line();
-                self.saida += &format!("for ({} = ", filhos[0].texto());
+                let mut controle = filhos[0].texto();
+                if self.referencias.contains(&controle) {
+                    controle = format!("(*{})", controle);
+                }
+                self.saida += &format!("for ({} = ", controle);
                 self.visit(&filhos[1]);
-                self.saida += &format!("; {} <= ", filhos[0].texto());
+                self.saida += &format!("; {} <= ", controle);
                 self.visit(&filhos[2]);
-                self.saida += &format!("; {}++) {{", filhos[0].texto());
+                self.saida += &format!("; {}++) {{", controle);
                 self.identacao += 1;
                 self.visit(&filhos[3]);
                 self.identacao -= 1;
@@ -410,7 +833,7 @@ impl Visitor for Gerador {
             // cmdAtribuicao : circunflexo identificador '<-' expressao
             RegraAST::CMDAtribuicao => {
                 self.new_line();
-                if filhos[2].tipo(&escopos) == TipoSimbolo::Cadeia {
+                if filhos[2].tipo(&self.escopos) == TipoSimbolo::Cadeia {
                     self.saida += "strcpy(";
                     self.visit(&filhos[1]);
                     self.saida += ", ";
@@ -428,13 +851,37 @@ impl Visitor for Gerador {
             // cmdChamada : IDENT '(' expressao expressoes ')'
             RegraAST::CMDChamada => {
                 self.new_line();
+                if let Some(embutida) = no.embutida(&self.escopos) {
+                    // argumentos sao retornados em ordem inversa
+                    let mut argumentos = no.variaveis();
+                    argumentos.reverse();
+                    self.chamada_embutida(embutida, &argumentos);
+                    return
+                }
                 self.visit(&filhos[0]);
                 self.saida += "(";
-                self.visit(&filhos[1]);
-                self.visit(&filhos[2]);
+                self.argumentos(no);
                 self.saida += ");";
             }
 
+            // cmdAfirme : 'afirme' expressao
+            RegraAST::CMDAfirme(texto) => {
+                // texto original da expressao, escapado para uma cadeia em C
+                let texto = texto.replace('\\', "\\\\").replace('"', "\\\"");
+                self.new_line();
+                self.saida += "if (!(";
+                self.visit(&filhos[0]);
+                self.saida += ")) {";
+                self.identacao += 1;
+                self.new_line();
+                self.saida += &format!("fputs(\"Linha {}: asserção falhou: {}\\n\", stderr);", no.linha(), texto);
+                self.new_line();
+                self.saida += "exit(1);";
+                self.identacao -= 1;
+                self.new_line();
+                self.saida += "}";
+            }
+
             // cmdRetorne : 'retorne' expressao
             RegraAST::CMDRetorne => {
                 self.new_line();
@@ -455,10 +902,19 @@ impl Visitor for Gerador {
 
             // numero_intervalo : op_unario NUM_INT numero_intervalo2
             RegraAST::NumeroIntervalo => {
-                let (num1, num2) = no.intervalo();
+                let (num1, num2) = no.intervalo(&self.escopos);
+                let valores = match self.escopos.tipos().resolver(filhos[1].tipo(&self.escopos)) {
+                    TipoSimbolo::Enumeracao(id) => self.escopos.tipos().enumeracao(id).valores.clone(),
+                    _ => vec![]
+                };
+                let caractere = filhos[1].tipo(&self.escopos) == TipoSimbolo::Caractere;
                 for i in num1..num2 + 1 {
                     self.new_line();
-                    self.saida += &format!("case {}:", i);
+                    match (valores.get(i as usize), char::from_u32(i as u32)) {
+                        (Some(valor), _) => self.saida += &format!("case {}:", valor),
+                        (None, Some(c)) if caractere && c.is_ascii_alphanumeric() => self.saida += &format!("case '{}':", c),
+                        _ => self.saida += &format!("case {}:", i)
+                    }
                 }
             }
 
@@ -472,25 +928,61 @@ impl Visitor for Gerador {
                 self.saida += &format!(" {} ", token.lexema());
             }
 
-            // op2 : '*' | '/'
+            // op2 : '*' | '/' | 'div'
             RegraAST::Op2 (token) => {
-                self.saida += &format!(" {} ", token.lexema());
+                // div so aceita inteiros, entao corresponde a divisao inteira do C
+                match token.tipo() {
+                    TipoToken::PCdiv => self.saida += " / ",
+                    _ => self.saida += &format!(" {} ", token.lexema())
+                }
             }
 
-            // op3 : '%'
+            // op3 : '%' | 'mod'
             RegraAST::Op3 => {
                 self.saida += " % ";
             }
 
-            // parcela_unario : IDENT '(' expressao expressoes ')'
-            RegraAST::ParcelaUnario2 => {
+            // fator_potencia : parcela_unario '**' op_unario fator_potencia
+            RegraAST::Potencia => {
+                // reaproveita a funcao embutida potencia, que ja inclui math.h
+                let potencia = biblioteca::buscar("potencia").unwrap();
+                self.usar(potencia);
+                self.saida += &format!("{}(", potencia.nome_c);
                 self.visit(&filhos[0]);
-                self.saida += "(";
+                self.saida += ", ";
                 self.visit(&filhos[1]);
                 self.visit(&filhos[2]);
                 self.saida += ")";
             }
 
+            // parcela_unario : IDENT '(' expressao expressoes ')'
+            RegraAST::ParcelaUnario2 => {
+                match no.embutida(&self.escopos) {
+                    Some(embutida) => {
+                        self.usar(embutida);
+                        self.saida += embutida.nome_c;
+                    }
+                    _ => if let TipoSimbolo::Enumeracao(_) = self.escopos.tipos().resolver(filhos[0].tipo(&self.escopos)) {
+                        // conversao explicita de inteiro para enumeracao
+                        self.saida += "(";
+                        self.visit(&filhos[0]);
+                        self.saida += ") ";
+                    } else {
+                        self.visit(&filhos[0])
+                    }
+                }
+                self.saida += "(";
+                self.argumentos(no);
+                self.saida += ")";
+            }
+
+            //     | '(' expressao ')'
+            RegraAST::ParcelaUnario3 => {
+                self.saida += "(";
+                self.visit(&filhos[0]);
+                self.saida += ")";
+            }
+
             //     | NUM_INT
             RegraAST::NumInt (token) => {
                 self.saida += &token.lexema();
@@ -512,6 +1004,50 @@ impl Visitor for Gerador {
                 self.saida += &token.lexema();
             }
 
+            //     | CARACTERE
+            RegraAST::Caractere (token) => {
+                self.saida += &token.lexema();
+            }
+
+            // exp_relacional : exp_aritmetica exp_relacional2
+            RegraAST::ExpRelacional => {
+                let comparacao = *filhos[1].regra() != RegraAST::Vazio;
+                match filhos[0].tipo_valor(&self.escopos) {
+                    // registros sao comparados por uma funcao propria, que recebe o endereco dos operandos,
+                    // e operandos que nao sao variaveis sao copiados para vetores temporarios, sendo avaliados uma unica vez
+                    TipoSimbolo::Registro(id) if comparacao => {
+                        let funcao = self.funcao_igualdade(id);
+                        let tipo = self.tipo_c(&TipoSimbolo::Registro(id));
+                        let operandos = [&filhos[0], &filhos[1].filhos()[1]].map(|operando| {
+                            let codigo = self.codigo(operando);
+                            if operando.identificador().is_some() {
+                                format!("&{}", codigo)
+                            } else {
+                                format!("({}[]){{{}}}", tipo, codigo)
+                            }
+                        });
+                        if filhos[1].filhos()[0].texto().trim() == "<>" {
+                            self.saida += "!";
+                        }
+                        self.saida += &format!("{}({}, {})", funcao, operandos[0], operandos[1]);
+                    }
+                    // literais sao comparados pelo conteudo, e nao pelo endereco
+                    TipoSimbolo::Cadeia if comparacao => {
+                        self.saida += "strcmp(";
+                        self.visit(&filhos[0]);
+                        self.saida += ", ";
+                        self.visit(&filhos[1].filhos()[1]);
+                        self.saida += ")";
+                        self.visit(&filhos[1].filhos()[0]);
+                        self.saida += "0";
+                    }
+                    _ => {
+                        self.visit(&filhos[0]);
+                        self.visit(&filhos[1]);
+                    }
+                }
+            }
+
             // op_relacional : '=' | '<>' | '>=' | '<=' | '>' | '<'
             RegraAST::OpRelacional (token) => {
                 self.saida += &match token.lexema().as_ref() {
@@ -548,8 +1084,8 @@ impl Visitor for Gerador {
             // constante_logica : 'verdadeiro' | 'falso'
             RegraAST::ConstanteLogica(token) => {
                 self.saida += match token.lexema().as_ref() {
-                    "verdadeiro" => "true",
-                    "falso" => "false",
+                    "verdadeiro" => "1",
+                    "falso" => "0",
                     _ => ""
                 }
             }
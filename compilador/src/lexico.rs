@@ -4,10 +4,15 @@ pub mod token;
 use scanner::Scanner;
 use token::{Token, TipoToken};
 
+use crate::diagnostico::{Codigo, Diagnostico, Trecho};
+
 /// analisador lexico
 pub struct Lexico {
     scanner: Scanner,
-    line: u32
+    line: u32,
+    // coluna do proximo caractere a ser confirmado
+    coluna: u32,
+    diagnosticos: Vec<Diagnostico>,
 }
 
 impl Lexico {
@@ -16,7 +21,9 @@ impl Lexico {
     pub fn new(file: &str) -> Self {
         Lexico {
             scanner: Scanner::new(file),
-            line: 1
+            line: 1,
+            coluna: 1,
+            diagnosticos: vec![],
         }
     }
 
@@ -25,95 +32,81 @@ impl Lexico {
         self.line
     }
 
-    /// debug: exibe conteúdo do buffer
-    pub fn print_buffer(&mut self) {
-        self.scanner.print_buffer();
+    /// retorna erros lexicos encontrados ate o momento, na ordem em que seus tokens foram lidos
+    pub fn diagnosticos(&self) -> &[Diagnostico] {
+        &self.diagnosticos
     }
 
-    /// retorna proximo token ou token de erro no caso de erro lexico
-    pub fn next_token(&mut self) -> Token {
-        let mut next = self.whitespace_and_comments();
-        self.scanner.confirm();
-        if next.is_some() {
-            return next.unwrap();
-        }
-        
-        next = self.end();
-        if next.is_none() {
-            self.scanner.reset();
-        } else {
-            self.scanner.confirm();
-            return next.unwrap();
-        }
-
-        next = self.keywords();
-        if next.is_none() {
-            self.scanner.reset();
-        } else {
-            self.scanner.confirm();
-            return next.unwrap();
-        }
+    /// registra erro lexico e retorna token de erro com sua mensagem
+    fn erro(&mut self, codigo: Codigo, mensagem: String) -> Token {
+        let token = Token::new(TipoToken::Erro, mensagem.clone(), self.line).com_coluna(self.coluna);
+        let trecho = Trecho::new(self.line, self.coluna, self.scanner.get_lexeme().chars().count() as u32);
+        self.diagnosticos.push(Diagnostico::erro(codigo, trecho, mensagem));
+        token
+    }
 
-        next = self.identifier();
-        if next.is_none() {
-            self.scanner.reset();
-        } else {
-            self.scanner.confirm();
-            return next.unwrap();
+    /// confirma lexema atual, avancando a coluna ate o fim dele
+    fn avancar(&mut self) {
+        for c in self.scanner.get_lexeme().chars() {
+            if c == '\n' {
+                self.coluna = 1;
+            } else {
+                self.coluna += 1;
+            }
         }
+        self.scanner.confirm();
+    }
 
-        next = self.number();
-        if next.is_none() {
-            self.scanner.reset();
-        } else {
-            self.scanner.confirm();
-            return next.unwrap();
-        }
+    /// confirma lexema de `token`, registrando a coluna em que ele comeca
+    fn confirmar(&mut self, token: Token) -> Token {
+        let token = token.com_coluna(self.coluna);
+        self.avancar();
+        token
+    }
 
-        next = self.op_arit();
-        if next.is_none() {
-            self.scanner.reset();
-        } else {
-            self.scanner.confirm();
-            return next.unwrap();
-        }
+    /// debug: exibe conteúdo do buffer
+    pub fn print_buffer(&mut self) {
+        self.scanner.print_buffer();
+    }
 
-        next = self.op_rel();
-        if next.is_none() {
-            self.scanner.reset();
-        } else {
-            self.scanner.confirm();
-            return next.unwrap();
+    /// retorna proximo token ou token de erro no caso de erro lexico
+    pub fn next_token(&mut self) -> Token {
+        let erro = self.whitespace_and_comments();
+        self.avancar();
+        if let Some(token) = erro {
+            return token
         }
 
-        next = self.special_char();
-        if next.is_none() {
-            self.scanner.reset();
-        } else {
-            self.scanner.confirm();
-            return next.unwrap();
+        // cada reconhecedor tenta formar um token a partir do inicio do lexema, que eh restaurado em caso de falha
+        let reconhecedores: [fn(&mut Self) -> Option<Token>; 9] = [
+            Self::end, Self::keywords, Self::identifier, Self::number, Self::op_arit,
+            Self::op_rel, Self::special_char, Self::str_literal, Self::char_literal,
+        ];
+        for reconhecedor in reconhecedores {
+            match reconhecedor(self) {
+                Some(token) => return self.confirmar(token),
+                None => self.scanner.reset(),
+            }
         }
 
-        next = self.str_literal();
-        if next.is_none() {
-            self.scanner.reset();
-        } else {
-            self.scanner.confirm();
-            return next.unwrap();
-        }
-        
-        let message = format!("Linha {}: {} - simbolo nao identificado\n", self.line, self.scanner.next_char());
-        return Token::new(TipoToken::Erro, message, self.line)
+        let simbolo = self.scanner.next_char();
+        self.erro(Codigo::SimboloNaoIdentificado, format!("{} - simbolo nao identificado", simbolo))
     }
 
     fn op_arit(&mut self) -> Option<Token> {
         let c = self.scanner.next_char();
         match c {
-            '*' => return Some(Token::new(TipoToken::OpAritMult, self.scanner.get_lexeme(), self.line)),
-            '/' => return Some(Token::new(TipoToken::OpAritDiv, self.scanner.get_lexeme(), self.line)),
-            '+' => return Some(Token::new(TipoToken::OpAritSoma, self.scanner.get_lexeme(), self.line)),
-            '-' => return Some(Token::new(TipoToken::OpAritSub, self.scanner.get_lexeme(), self.line)),
-            _ => return None
+            '*' => {
+                if self.scanner.next_char() == '*' {
+                    return Some(Token::new(TipoToken::OpAritPot, self.scanner.get_lexeme(), self.line))
+                }
+                self.scanner.decrement();
+                Some(Token::new(TipoToken::OpAritMult, self.scanner.get_lexeme(), self.line))
+            }
+            '/' => Some(Token::new(TipoToken::OpAritDiv, self.scanner.get_lexeme(), self.line)),
+            '+' => Some(Token::new(TipoToken::OpAritSoma, self.scanner.get_lexeme(), self.line)),
+            '-' => Some(Token::new(TipoToken::OpAritSub, self.scanner.get_lexeme(), self.line)),
+            _ => None
         }
     }
 
@@ -123,31 +116,31 @@ impl Lexico {
             '<' => {
                 c = self.scanner.next_char();
                 match c {
-                    '>' => return Some(Token::new(TipoToken::OpRelDif, self.scanner.get_lexeme(), self.line)),
-                    '=' => return Some(Token::new(TipoToken::OpRelMenorIgual, self.scanner.get_lexeme(), self.line)),
+                    '>' => Some(Token::new(TipoToken::OpRelDif, self.scanner.get_lexeme(), self.line)),
+                    '=' => Some(Token::new(TipoToken::OpRelMenorIgual, self.scanner.get_lexeme(), self.line)),
                     '-' => {
                         self.scanner.decrement();
                         self.scanner.decrement();
-                        return None;
+                        None
                     }
                     _ => {
                         self.scanner.decrement();
-                        return Some(Token::new(TipoToken::OpRelMenor, self.scanner.get_lexeme(), self.line));
+                        Some(Token::new(TipoToken::OpRelMenor, self.scanner.get_lexeme(), self.line))
                     }
                 }
             }
-            '=' => return Some(Token::new(TipoToken::OpRelIgual, self.scanner.get_lexeme(), self.line)),
+            '=' => Some(Token::new(TipoToken::OpRelIgual, self.scanner.get_lexeme(), self.line)),
             '>' => {
                 c = self.scanner.next_char();
                 match c {
-                    '=' => return Some(Token::new(TipoToken::OpRelMaiorIgual, self.scanner.get_lexeme(), self.line)),
+                    '=' => Some(Token::new(TipoToken::OpRelMaiorIgual, self.scanner.get_lexeme(), self.line)),
                     _ => {
                         self.scanner.decrement();
-                        return Some(Token::new(TipoToken::OpRelMaior, self.scanner.get_lexeme(), self.line));
+                        Some(Token::new(TipoToken::OpRelMaior, self.scanner.get_lexeme(), self.line))
                     }
                 }
             }
-            _ => return None
+            _ => None
         }
     }
 
@@ -156,7 +149,7 @@ impl Lexico {
         loop {
             let mut c = self.scanner.next_char();
             if state == 1 {
-                if c.is_digit(10) {
+                if c.is_ascii_digit() {
                     state = 2;
                 } else {
                     return None
@@ -164,7 +157,7 @@ impl Lexico {
             } else if state == 2 {
                 if c == '.' {
                     c = self.scanner.next_char();
-                    if c.is_digit(10) {
+                    if c.is_ascii_digit() {
                         state = 3;
                     } else if c == '.' {
                         self.scanner.decrement();
@@ -173,15 +166,13 @@ impl Lexico {
                     } else {
                         return None
                     }
-                } else if !(c.is_digit(10)) {
+                } else if !(c.is_ascii_digit()) {
                     self.scanner.decrement();
                     return Some(Token::new(TipoToken::NumInt, self.scanner.get_lexeme(), self.line));
                 }
-            } else if state == 3 {
-                if !(c.is_digit(10)) {
-                    self.scanner.decrement();
-                    return Some(Token::new(TipoToken::NumReal, self.scanner.get_lexeme(), self.line));
-                }
+            } else if state == 3 && !c.is_ascii_digit() {
+                self.scanner.decrement();
+                return Some(Token::new(TipoToken::NumReal, self.scanner.get_lexeme(), self.line));
             }
         }
     }
@@ -196,11 +187,9 @@ impl Lexico {
                 } else {
                     return None
                 }
-            } else if state == 2 {
-                if !(c.is_alphanumeric() || c == '_') {
-                    self.scanner.decrement();
-                    return Some(Token::new(TipoToken::Ident, self.scanner.get_lexeme(), self.line));
-                }
+            } else if state == 2 && !(c.is_alphanumeric() || c == '_') {
+                self.scanner.decrement();
+                return Some(Token::new(TipoToken::Ident, self.scanner.get_lexeme(), self.line));
             }
         }
     }
@@ -217,8 +206,7 @@ impl Lexico {
                 }
             } else if state == 2 {
                 if c == '\n' {
-                    let message = format!("Linha {}: cadeia literal nao fechada\n", self.line);
-                    return Some(Token::new(TipoToken::Erro, message, self.line))
+                    return Some(self.erro(Codigo::CadeiaNaoFechada, "cadeia literal nao fechada".to_string()))
                 }
                 if c == '\"' {
                     return Some(Token::new(TipoToken::Cadeia, self.scanner.get_lexeme(), self.line));
@@ -227,8 +215,7 @@ impl Lexico {
                 }
             } else if state == 3 {
                 if c == '\n' {
-                    let message = format!("Linha {}: cadeia literal nao fechada\n", self.line);
-                    return Some(Token::new(TipoToken::Erro, message, self.line))
+                    return Some(self.erro(Codigo::CadeiaNaoFechada, "cadeia literal nao fechada".to_string()))
                 } else {
                     state = 2;
                 }
@@ -236,6 +223,39 @@ impl Lexico {
         }
     }
 
+    fn char_literal(&mut self) -> Option<Token> {
+        let mut state = 1;
+        loop {
+            let c = self.scanner.next_char();
+            if state > 1 && c == '\n' {
+                return Some(self.erro(Codigo::CaractereNaoFechado, "caractere literal nao fechado".to_string()))
+            }
+            if state == 1 {
+                if c == '\'' {
+                    state = 2;
+                } else {
+                    return None
+                }
+            } else if state == 2 {
+                if c == '\\' {
+                    state = 3;
+                } else if c == '\'' {
+                    return Some(self.erro(Codigo::CaractereVazio, "caractere literal vazio".to_string()))
+                } else {
+                    state = 4;
+                }
+            } else if state == 3 {
+                state = 4;
+            } else if state == 4 {
+                if c == '\'' {
+                    return Some(Token::new(TipoToken::Caractere, self.scanner.get_lexeme(), self.line));
+                } else {
+                    return Some(self.erro(Codigo::CaractereNaoFechado, "caractere literal nao fechado".to_string()))
+                }
+            }
+        }
+    }
+
     fn whitespace_and_comments(&mut self) -> Option<Token> {
         let mut state = 1;
         loop {
@@ -259,8 +279,7 @@ impl Lexico {
             } else if state == 3 {
 
                 if c == '\n' {
-                    let message = format!("Linha {}: comentario nao fechado\n", self.line);
-                    return Some(Token::new(TipoToken::Erro, message, self.line))
+                    return Some(self.erro(Codigo::ComentarioNaoFechado, "comentario nao fechado".to_string()))
                 } else if c == '}' {
                     state = 1
                 }
@@ -274,34 +293,34 @@ impl Lexico {
     fn special_char(&mut self) -> Option<Token> {
         let mut c = self.scanner.next_char();
         match c {
-            '(' => return Some(Token::new(TipoToken::AbrePar, self.scanner.get_lexeme(), self.line)),
-            ')' => return Some(Token::new(TipoToken::FechaPar, self.scanner.get_lexeme(), self.line)),
-            '[' => return Some(Token::new(TipoToken::AbreCol, self.scanner.get_lexeme(), self.line)),
-            ']' => return Some(Token::new(TipoToken::FechaCol, self.scanner.get_lexeme(), self.line)),
-            ',' => return Some(Token::new(TipoToken::Virgula, self.scanner.get_lexeme(), self.line)),
-            '%' => return Some(Token::new(TipoToken::Porcento, self.scanner.get_lexeme(), self.line)),
-            ':' => return Some(Token::new(TipoToken::Delim, self.scanner.get_lexeme(), self.line)),
-            '^' => return Some(Token::new(TipoToken::Circunflexo, self.scanner.get_lexeme(), self.line)),
-            '&' => return Some(Token::new(TipoToken::EComercial, self.scanner.get_lexeme(), self.line)),
+            '(' => Some(Token::new(TipoToken::AbrePar, self.scanner.get_lexeme(), self.line)),
+            ')' => Some(Token::new(TipoToken::FechaPar, self.scanner.get_lexeme(), self.line)),
+            '[' => Some(Token::new(TipoToken::AbreCol, self.scanner.get_lexeme(), self.line)),
+            ']' => Some(Token::new(TipoToken::FechaCol, self.scanner.get_lexeme(), self.line)),
+            ',' => Some(Token::new(TipoToken::Virgula, self.scanner.get_lexeme(), self.line)),
+            '%' => Some(Token::new(TipoToken::Porcento, self.scanner.get_lexeme(), self.line)),
+            ':' => Some(Token::new(TipoToken::Delim, self.scanner.get_lexeme(), self.line)),
+            '^' => Some(Token::new(TipoToken::Circunflexo, self.scanner.get_lexeme(), self.line)),
+            '&' => Some(Token::new(TipoToken::EComercial, self.scanner.get_lexeme(), self.line)),
             '.' => {
                 c = self.scanner.next_char();
                 if c == '.' {
-                    return Some(Token::new(TipoToken::PontoPonto, self.scanner.get_lexeme(), self.line));
+                    Some(Token::new(TipoToken::PontoPonto, self.scanner.get_lexeme(), self.line))
                 } else {
                     self.scanner.decrement();
-                    return Some(Token::new(TipoToken::Ponto, self.scanner.get_lexeme(), self.line));
+                    Some(Token::new(TipoToken::Ponto, self.scanner.get_lexeme(), self.line))
                 }
             }
             '<' => {
                 c = self.scanner.next_char();
                 if c == '-' {
-                    return Some(Token::new(TipoToken::BackArrow, self.scanner.get_lexeme(), self.line));
+                    Some(Token::new(TipoToken::BackArrow, self.scanner.get_lexeme(), self.line))
                 } else {
                     self.scanner.decrement();
-                    return None
+                    None
                 }
             }
-            _ => return None
+            _ => None
         }
     }
 
@@ -321,6 +340,10 @@ impl Lexico {
                     "fim_algoritmo" => return Some(Token::new(TipoToken::PCfimAlgoritmo, self.scanner.get_lexeme(), self.line)), 
                     "real" => return Some(Token::new(TipoToken::PCreal, self.scanner.get_lexeme(), self.line)), 
                     "logico" => return Some(Token::new(TipoToken::PClogico, self.scanner.get_lexeme(), self.line)), 
+                    "arquivo" => return Some(Token::new(TipoToken::PCarquivo, self.scanner.get_lexeme(), self.line)), 
+                    "caractere" => return Some(Token::new(TipoToken::PCcaractere, self.scanner.get_lexeme(), self.line)), 
+                    "div" => return Some(Token::new(TipoToken::PCdiv, self.scanner.get_lexeme(), self.line)), 
+                    "mod" => return Some(Token::new(TipoToken::PCmod, self.scanner.get_lexeme(), self.line)), 
                     "fim_se" => return Some(Token::new(TipoToken::PCfimSe, self.scanner.get_lexeme(), self.line)), 
                     "senao" => return Some(Token::new(TipoToken::PCsenao, self.scanner.get_lexeme(), self.line)), 
                     "entao" => return Some(Token::new(TipoToken::PCentao, self.scanner.get_lexeme(), self.line)), 
@@ -343,6 +366,7 @@ impl Lexico {
                     "funcao" => return Some(Token::new(TipoToken::PCfuncao, self.scanner.get_lexeme(), self.line)), 
                     "fim_funcao" => return Some(Token::new(TipoToken::PCfimFuncao, self.scanner.get_lexeme(), self.line)), 
                     "retorne" => return Some(Token::new(TipoToken::PCretorne, self.scanner.get_lexeme(), self.line)), 
+                    "afirme" => return Some(Token::new(TipoToken::PCafirme, self.scanner.get_lexeme(), self.line)), 
                     "constante" => return Some(Token::new(TipoToken::PCconstante, self.scanner.get_lexeme(), self.line)), 
                     "falso" => return Some(Token::new(TipoToken::PCfalso, self.scanner.get_lexeme(), self.line)), 
                     "verdadeiro" => return Some(Token::new(TipoToken::PCverdadeiro, self.scanner.get_lexeme(), self.line)), 
@@ -360,6 +384,6 @@ impl Lexico {
         if c == '\0' {
             return Some(Token::new(TipoToken::Fim, "EOF".to_string(), self.line))
         }
-        return None
+        None
     }
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, ErrorKind, Read};
 
 const BUFFER_SIZE: usize = 500;
 
@@ -30,7 +30,18 @@ impl Scanner {
     fn load_buffer(&mut self, buffer_half: usize) {
         if self.current_buffer != buffer_half {
             self.current_buffer = buffer_half;
-            let n = self.stream.read(&mut self.buffer[buffer_half * BUFFER_SIZE..buffer_half * BUFFER_SIZE + BUFFER_SIZE]).unwrap();
+            let metade = &mut self.buffer[buffer_half * BUFFER_SIZE..buffer_half * BUFFER_SIZE + BUFFER_SIZE];
+            // read pode ler menos que o pedido, ou ser interrompido, antes do fim do arquivo,
+            // entao so uma leitura vazia indica o fim
+            let mut n = 0;
+            while n < BUFFER_SIZE {
+                match self.stream.read(&mut metade[n..]) {
+                    Ok(0) => break,
+                    Ok(lidos) => n += lidos,
+                    Err(erro) if erro.kind() == ErrorKind::Interrupted => {}
+                    Err(erro) => panic!("erro ao ler arquivo: {}", erro),
+                }
+            }
             if n < BUFFER_SIZE {
                 self.buffer[self.current_buffer * BUFFER_SIZE + n] = b'\0';
             }
@@ -1,13 +1,15 @@
+use crate::diagnostico::Trecho;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum TipoToken {
     PCalgoritmo, PCdeclare, PCinteiro, PCleia, PCescreva, PCfimAlgoritmo, PCreal,
     PCliteral, PClogico, PCfimSe, PCsenao, PCentao, PCse, PCfimCaso, PCpara, PCate, PCfaca,
     PCfimPara, PCfimEnquanto, PCseja, PCcaso, PCenquanto, PCregistro, PCfimRegistro,
-    PCtipo, PCfimProcedimento, PCprocedimento, PCvar, PCfuncao, PCfimFuncao, PCretorne, PCconstante,
-    PCfalso, PCverdadeiro, PCnao, PCou, PCe, OpAritMult, OpAritDiv, OpAritSoma,
+    PCtipo, PCfimProcedimento, PCprocedimento, PCvar, PCfuncao, PCfimFuncao, PCretorne, PCafirme, PCconstante,
+    PCfalso, PCverdadeiro, PCnao, PCou, PCe, PCarquivo, PCcaractere, PCdiv, PCmod, OpAritMult, OpAritPot, OpAritDiv, OpAritSoma,
     OpAritSub, OpRelMenor, OpRelMenorIgual, OpRelMaiorIgual, BackArrow, OpRelMaior, OpRelIgual,
     OpRelDif, Delim, AbrePar, FechaPar, AbreCol, FechaCol, Virgula, Porcento, Circunflexo,
-    EComercial, Ponto, PontoPonto, Ident, NumInt, NumReal, Cadeia, Fim, Erro, Vazio,
+    EComercial, Ponto, PontoPonto, Ident, NumInt, NumReal, Cadeia, Caractere, Fim, Erro, Vazio,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -15,6 +17,8 @@ pub struct Token {
     tipo: TipoToken,
     lexema: String,
     linha: u32,
+    // coluna do primeiro caractere do lexema, 0 caso desconhecida
+    coluna: u32,
 }
 
 impl Token {
@@ -25,9 +29,16 @@ impl Token {
             tipo,
             lexema,
             linha,
+            coluna: 0,
         }
     }
 
+    /// retorna o token com a coluna em que seu lexema comeca
+    pub fn com_coluna(mut self, coluna: u32) -> Self {
+        self.coluna = coluna;
+        self
+    }
+
     /// get tipo
     pub fn tipo(&self) -> TipoToken {
         self.tipo
@@ -43,6 +54,11 @@ impl Token {
         self.linha
     }
 
+    /// retorna trecho do arquivo fonte ocupado pelo lexema
+    pub fn trecho(&self) -> Trecho {
+        Trecho::new(self.linha, self.coluna, self.lexema.chars().count() as u32)
+    }
+
     /// retorna string representando o tipo do token
     pub fn tipo_string(&self) -> String {
         match self.tipo {
@@ -50,6 +66,7 @@ impl Token {
             TipoToken::NumInt => "NUM_INT".to_string(),
             TipoToken::NumReal => "NUM_REAL".to_string(),
             TipoToken::Cadeia => "CADEIA".to_string(),
+            TipoToken::Caractere => "CARACTERE".to_string(),
             _ => "'".to_string() + &self.lexema + "'"
         }
     }
@@ -63,6 +80,6 @@ impl Token {
 
     /// retorna uma copia do token
     pub fn copy(&mut self) -> Token {
-        Self::new(self.tipo(), self.lexema(), self.linha)
+        Self::new(self.tipo(), self.lexema(), self.linha).com_coluna(self.coluna)
     }
 }
\ No newline at end of file
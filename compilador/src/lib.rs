@@ -1,3 +1,4 @@
+pub mod diagnostico;
 pub mod lexico;
 pub mod sintatico;
 pub mod semantico;
@@ -1,17 +1,28 @@
 pub mod visitor;
 pub mod tabela_de_simbolos;
 pub mod escopos;
+pub mod biblioteca;
+pub mod fluxo;
+pub mod tipos;
 
+use biblioteca::{Embutida, Variadica};
 use escopos::Escopos;
-use tabela_de_simbolos::TipoSimbolo;
+use fluxo::AtribuicaoDefinida;
+use tabela_de_simbolos::{Categoria, TipoSimbolo};
+use tipos::{Parametro, TabelaDeTipos};
 use visitor::Visitor;
 
+use crate::diagnostico::{Codigo, Diagnostico, Trecho};
+use crate::lexico::token::Token;
 use crate::sintatico::arvore_sintatica::{NoAST, RegraAST};
 
 /// analisador semantico
 pub struct Semantico {
     escopos: Escopos,
-    erros: Vec<String>,
+    diagnosticos: Vec<Diagnostico>,
+    registros_adiante: Vec<String>,
+    // em modo estrito, o resultado de uma funcao nao pode ser descartado
+    estrito: bool,
 }
 
 impl Semantico {
@@ -19,69 +30,505 @@ impl Semantico {
     /// retorna instancia de analisador semantico
     pub fn new() -> Self {
         Self {
-            escopos: Escopos::new(TipoSimbolo::Vazio),
-            erros: vec![],
+            escopos: Escopos::com_biblioteca(),
+            diagnosticos: vec![],
+            registros_adiante: vec![],
+            estrito: false,
+        }
+    }
+
+    /// retorna instancia de analisador semantico em modo estrito, que rejeita chamadas de funcao usadas como comando
+    pub fn estrito() -> Self {
+        Self {
+            estrito: true,
+            ..Self::new()
+        }
+    }
+
+    /// retorna instancia de analisador semantico configurada pelos argumentos de linha de comando, em que `--estrito` ativa o modo estrito
+    pub fn com_argumentos(args: &[String]) -> Self {
+        if args.iter().any(|arg| arg == "--estrito") {
+            Self::estrito()
+        } else {
+            Self::new()
         }
     }
 
     /// retorna vetor de erros semanticos
-    pub fn get_erros(&self) -> Vec<String> {
-        self.erros.clone()
+    pub fn get_erros(&self) -> Vec<Diagnostico> {
+        self.diagnosticos.iter().filter(|diagnostico| diagnostico.is_erro()).cloned().collect()
+    }
+
+    /// retorna vetor de avisos, que nao impedem a geracao de codigo
+    pub fn get_avisos(&self) -> Vec<Diagnostico> {
+        self.diagnosticos.iter().filter(|diagnostico| !diagnostico.is_erro()).cloned().collect()
+    }
+
+    /// retorna tabela dos tipos declarados pelo programa, para que o gerador de codigo os reutilize
+    pub fn get_tipos(&self) -> TabelaDeTipos {
+        self.escopos.tipos().clone()
+    }
+
+    /// registra erro semantico em `trecho`
+    fn erro(&mut self, codigo: Codigo, trecho: Trecho, mensagem: String) {
+        self.diagnosticos.push(Diagnostico::erro(codigo, trecho, mensagem));
+    }
+
+    /// registra aviso em `trecho`
+    fn aviso(&mut self, codigo: Codigo, trecho: Trecho, mensagem: String) {
+        self.diagnosticos.push(Diagnostico::aviso(codigo, trecho, mensagem));
+    }
+
+    /// registra erro de `ident` ja declarado no escopo atual, com uma nota indicando a declaracao anterior
+    fn erro_ja_declarado(&mut self, ident: &Token) {
+        let nome = ident.lexema();
+        let mut diagnostico = Diagnostico::erro(Codigo::IdentificadorJaDeclarado, ident.trecho(), format!("identificador {} ja declarado anteriormente", nome));
+        if let Some(anterior) = self.escopos.escopo_atual().verificar(&nome).filter(|simbolo| simbolo.linha() > 0) {
+            diagnostico = diagnostico.com_nota(format!("{} foi declarado na linha {}", nome, anterior.linha()));
+        }
+        self.diagnosticos.push(diagnostico);
+    }
+
+    /// registra uso do simbolo `nome`\
+    /// receber um valor so conta como uso de parametros, ja que o valor de uma variavel que nunca eh lida nao tem efeito
+    fn usar(&mut self, nome: &str, leitura: bool) {
+        if let Some(simbolo) = self.escopos.verificar_mut(nome) {
+            if leitura || simbolo.categoria() == Categoria::Parametro {
+                simbolo.marcar_uso();
+            }
+        }
+    }
+
+    /// avisa sobre variaveis, parametros, tipos e constantes do escopo atual que nunca foram usados
+    fn avisar_nao_usados(&mut self) {
+        for simbolo in self.escopos.escopo_atual().simbolos() {
+            if simbolo.usado() {
+                continue
+            }
+            let nome = simbolo.nome();
+            let (codigo, mensagem) = match simbolo.categoria() {
+                Categoria::Variavel => (Codigo::VariavelNaoLida, format!("variavel {} declarada mas nunca lida", nome)),
+                Categoria::Parametro => (Codigo::ParametroNaoUsado, format!("parametro {} nunca usado", nome)),
+                Categoria::Tipo => (Codigo::TipoNaoUsado, format!("tipo {} nunca usado", nome)),
+                Categoria::Constante => (Codigo::ConstanteNaoUsada, format!("constante {} nunca usada", nome)),
+                _ => continue
+            };
+            self.aviso(codigo, simbolo.trecho(), mensagem);
+        }
+    }
+
+    /// declara os campos de `nome`, caso seja um registro, como simbolos `nome.campo` no escopo atual
+    fn declarar_campos(&mut self, nome: &str, tipo: &TipoSimbolo, trecho: Trecho) {
+        let campos = self.escopos.tipos().campos(tipo);
+        let escopo_atual = self.escopos.escopo_atual();
+        for campo in campos {
+            escopo_atual.declarar_vetor(&format!("{}.{}", nome, campo.nome), &campo.tipo, Categoria::Campo, trecho, campo.tamanhos);
+        }
+    }
+
+    /// verifica se o valor inicial de `identificador` corresponde ao seu tipo e a suas dimensoes
+    fn verificar_inicializacao(&mut self, identificador: &NoAST, tipo: &TipoSimbolo, valor: &NoAST) {
+        let ident = identificador.filhos()[0].token().unwrap();
+        let dimensoes = identificador.dimensoes();
+
+        let compativel = if let RegraAST::ValoresIniciais = valor.regra() {
+            let valores = valor.variaveis();
+            if dimensoes.len() != 1 {
+                false
+            } else if dimensoes[0].texto().parse().is_ok_and(|tamanho: usize| tamanho != valores.len()) {
+                self.erro(Codigo::QuantidadeDeValores, ident.trecho(), format!("quantidade de valores incompativel na inicializacao de {}", ident.lexema()));
+                return
+            } else {
+                valores.iter().all(|valor| compativel_atribuicao(tipo, &valor.tipo_nominal(&self.escopos), self.escopos.tipos()))
+            }
+        } else {
+            dimensoes.is_empty() && compativel_atribuicao(tipo, &valor.tipo_nominal(&self.escopos), self.escopos.tipos())
+        };
+
+        if !compativel {
+            self.erro(Codigo::InicializacaoIncompativel, ident.trecho(), format!("inicializacao nao compativel para {}", ident.lexema()));
+        }
+    }
+
+    /// verifica se `identificador` pode receber um valor, o que nao ocorre com constantes, tipos, procedimentos e funcoes\
+    /// `identificador` pode ser tambem o proprio IDENT, como a variavel de controle de um para
+    fn verificar_atribuivel(&mut self, identificador: &NoAST) {
+        if !self.atribuivel(identificador) {
+            let ident = identificador.token().or_else(|| identificador.filhos()[0].token()).unwrap();
+            self.erro(Codigo::NaoAtribuivel, ident.trecho(), format!("{} nao pode receber um valor", ident.lexema()));
+        }
+    }
+
+    /// verifica se `identificador` nao eh uma constante, tipo, procedimento ou funcao
+    fn atribuivel(&self, identificador: &NoAST) -> bool {
+        let ident = identificador.token().or_else(|| identificador.filhos()[0].token()).unwrap();
+        let categoria = self.escopos.verificar(&ident.lexema()).map(|simbolo| simbolo.categoria());
+        !matches!(categoria, Some(Categoria::Constante | Categoria::ValorEnumerado | Categoria::Tipo | Categoria::Procedimento | Categoria::Funcao))
+    }
+
+    /// verifica um identificador segundo a forma `uso` como ele aparece no programa
+    fn verificar_identificador(&mut self, identificador: &NoAST, uso: Uso) {
+        let filhos = identificador.filhos();
+        let nome = filhos[0].texto() + &filhos[1].texto();
+
+        match uso {
+            Uso::Declaracao => {}
+            Uso::Atribuicao => {
+                self.usar(&filhos[0].texto(), false);
+                self.verificar_atribuivel(identificador);
+            }
+            Uso::Leitura | Uso::Argumento => {
+                self.usar(&filhos[0].texto(), true);
+                let categoria = self.escopos.verificar(&filhos[0].texto()).map(|simbolo| simbolo.categoria());
+                if let Some(Categoria::Tipo | Categoria::Procedimento | Categoria::Funcao) = categoria {
+                    self.erro(Codigo::NaoEhValor, identificador.trecho(), format!("{} nao eh um valor", filhos[0].texto()));
+                }
+            }
+        }
+
+        match identificador.declaracao(&self.escopos) {
+            None => {
+                let ident = filhos[0].token().unwrap();
+                self.erro(Codigo::IdentificadorNaoDeclarado, ident.trecho(), format!("identificador {} nao declarado", nome));
+            }
+            // em declaracoes, as dimensoes sao tamanhos, e nao indices
+            Some((tipo, tamanhos)) if uso != Uso::Declaracao => self.verificar_indices(identificador, &nome, &tipo, &tamanhos, uso != Uso::Argumento),
+            Some(_) => {}
+        }
+    }
+
+    /// verifica tipo, quantidade e limites dos indices de `identificador`, declarado com dimensoes de tamanhos `tamanhos`\
+    /// um indice alem das dimensoes declaradas de um literal seleciona um de seus caracteres, e
+    /// quando `escalar` eh verdadeiro, todas as dimensoes devem ser indexadas
+    fn verificar_indices(&mut self, identificador: &NoAST, nome: &str, tipo: &TipoSimbolo, tamanhos: &[Option<i32>], escalar: bool) {
+        let indices = identificador.dimensoes();
+        let trecho = identificador.trecho();
+
+        for indice in &indices {
+            if !matches!(indice.tipo_valor(&self.escopos), TipoSimbolo::Inteiro | TipoSimbolo::Invalido) {
+                self.erro(Codigo::IndiceInvalido, trecho, format!("indice de {} deve ser inteiro", nome));
+            }
+        }
+
+        let limite = tamanhos.len() + if *tipo == TipoSimbolo::Cadeia { 1 } else { 0 };
+        if indices.len() > limite {
+            if tamanhos.is_empty() && *tipo != TipoSimbolo::Cadeia {
+                self.erro(Codigo::DimensoesIncompativeis, trecho, format!("{} nao eh um vetor", nome));
+            } else {
+                self.erro(Codigo::DimensoesIncompativeis, trecho, format!("quantidade de indices incompativel para {}", nome));
+            }
+            return
+        }
+        if escalar && indices.len() < tamanhos.len() {
+            self.erro(Codigo::DimensoesIncompativeis, trecho, format!("quantidade de indices incompativel para {}", nome));
+            return
+        }
+
+        for (indice, tamanho) in indices.iter().zip(tamanhos) {
+            if let (Some(valor), Some(tamanho)) = (indice.valor_inteiro(&self.escopos), tamanho) {
+                if valor < 0 || valor >= *tamanho {
+                    self.erro(Codigo::IndiceForaDosLimites, trecho, format!("indice {} fora dos limites de {}", valor, nome));
+                }
+            }
+        }
+    }
+
+    /// verifica rotulos de um comando caso, que nao podem estar invertidos nem se sobrepor\
+    /// sem 'senao', um seletor enumerado deve ter todos os seus valores cobertos pelos rotulos
+    fn verificar_rotulos(&mut self, caso: &NoAST) {
+        let filhos = caso.filhos();
+        let mut intervalos: Vec<(i32, i32)> = vec![];
+
+        // selecao : item_selecao selecao | <<vazio>>
+        // item_selecao : constantes ':' cmds
+        // constantes : numero_intervalo numero_intervalos
+        let mut selecao = &filhos[1];
+        while let RegraAST::Selecao = selecao.regra() {
+            let constantes = &selecao.filhos()[0].filhos()[0];
+            let mut numeros = vec![&constantes.filhos()[0]];
+            let mut resto = &constantes.filhos()[1];
+            while let RegraAST::NumeroIntervalos = resto.regra() {
+                numeros.push(&resto.filhos()[0]);
+                resto = &resto.filhos()[1];
+            }
+            selecao = &selecao.filhos()[1];
+
+            for numero in numeros {
+                let extremos = [&numero.filhos()[1], numero.filhos()[2].filhos().get(1).unwrap_or(&numero.filhos()[1])];
+                // constantes nao enumeradas ja foram reportadas
+                if extremos.iter().any(|extremo| matches!(extremo.regra(), RegraAST::Ident(_)) && !matches!(extremo.tipo(&self.escopos), TipoSimbolo::Enumeracao(_))) {
+                    continue
+                }
+
+                let rotulo = numero.filhos()[0].texto() + &numero.filhos()[1].texto();
+                let rotulo = match numero.filhos()[2].regra() {
+                    RegraAST::Vazio => rotulo,
+                    _ => format!("{}..{}", rotulo, numero.filhos()[2].texto()),
+                };
+                let (inicio, fim) = numero.intervalo(&self.escopos);
+                if inicio > fim {
+                    self.erro(Codigo::RotuloInvalido, numero.trecho(), format!("intervalo {} invertido", rotulo));
+                } else if intervalos.iter().any(|(outro_inicio, outro_fim)| inicio <= *outro_fim && *outro_inicio <= fim) {
+                    self.erro(Codigo::RotuloInvalido, numero.trecho(), format!("rotulo {} repetido no caso", rotulo));
+                } else {
+                    intervalos.push((inicio, fim));
+                }
+            }
+        }
+
+        if let (TipoSimbolo::Enumeracao(id), RegraAST::Vazio) = (filhos[0].tipo_valor(&self.escopos), filhos[2].regra()) {
+            let faltantes: Vec<String> = self.escopos.tipos().enumeracao(id).valores.clone().into_iter()
+                .enumerate()
+                .filter(|(i, _)| !intervalos.iter().any(|(inicio, fim)| (*inicio..=*fim).contains(&(*i as i32))))
+                .map(|(_, valor)| valor)
+                .collect();
+            if !faltantes.is_empty() {
+                self.aviso(Codigo::CasoIncompleto, caso.trecho(), format!("caso sem senao nao trata os valores {}", faltantes.join(", ")));
+            }
+        }
+    }
+
+    /// verifica se `condicao` eh uma expressao logica apos percorre-la, relatando `mensagem` caso contrario\
+    /// condicoes que ja produziram erros ao serem percorridas, como as que usam identificadores nao declarados, nao sao relatadas novamente
+    fn verificar_condicao(&mut self, condicao: &NoAST, codigo: Codigo, mensagem: &str) {
+        let erros = self.quantidade_erros();
+        self.traverse(condicao);
+        if self.quantidade_erros() == erros && condicao.tipo_valor(&self.escopos) != TipoSimbolo::Logico {
+            self.erro(codigo, condicao.trecho(), mensagem.to_string());
+        }
+    }
+
+    /// percorre os argumentos `expressoes` de uma chamada, a partir do segundo
+    fn percorrer_argumentos(&mut self, expressoes: &NoAST) {
+        self.visit(expressoes);
+        // expressoes : ',' expressao expressoes | <<vazio>>
+        if let RegraAST::Expressoes = expressoes.regra() {
+            self.percorrer_argumento(&expressoes.filhos()[0]);
+            self.percorrer_argumentos(&expressoes.filhos()[1]);
+        }
+    }
+
+    /// percorre um argumento de chamada, que pode ser um vetor nao indexado caso seja formado apenas por um identificador
+    fn percorrer_argumento(&mut self, argumento: &NoAST) {
+        if argumento.identificador().is_none() {
+            return self.traverse(argumento)
+        }
+        if let RegraAST::Identificador = argumento.regra() {
+            self.verificar_identificador(argumento, Uso::Argumento);
+            for filho in argumento.filhos() {
+                self.traverse(filho)
+            }
+            return
+        }
+        self.visit(argumento);
+        for filho in argumento.filhos() {
+            self.percorrer_argumento(filho)
+        }
+    }
+
+    /// retorna quantidade de erros relatados ate o momento
+    fn quantidade_erros(&self) -> usize {
+        self.diagnosticos.iter().filter(|diagnostico| diagnostico.is_erro()).count()
+    }
+
+    /// verifica quantidade e tipos dos argumentos de uma chamada a funcao ou procedimento declarado no programa
+    fn verificar_argumentos(&mut self, no: &NoAST, parametros: &[Parametro]) {
+        // argumentos sao retornados em ordem inversa
+        let mut argumentos = no.variaveis();
+        argumentos.reverse();
+
+        if !no.argumentos_compativeis(parametros, &self.escopos) {
+            self.erro(Codigo::ParametrosIncompativeis, no.trecho(), format!("incompatibilidade de parametros na chamada de {}", no.filhos()[0].texto()));
+            return
+        }
+
+        // argumentos passados a parametros 'var' recebem valor, e portanto devem ser variaveis
+        for (argumento, parametro) in argumentos.iter().zip(parametros) {
+            match argumento.identificador() {
+                Some(identificador) if parametro.referencia => self.verificar_atribuivel(identificador),
+                None if parametro.referencia => self.erro(Codigo::NaoAtribuivel, no.trecho(), format!("argumento passado a parametro var na chamada de {} deve ser uma variavel", no.filhos()[0].texto())),
+                _ => {}
+            }
+        }
+    }
+
+    /// verifica quantidade, tipos e modo de passagem dos argumentos de uma chamada a funcao embutida
+    fn verificar_embutida(&mut self, no: &NoAST, embutida: &Embutida) {
+        // argumentos sao retornados em ordem inversa
+        let mut argumentos = no.variaveis();
+        argumentos.reverse();
+        let parametros = embutida.tipos_parametros();
+
+        let tipos: Vec<TipoSimbolo> = argumentos.iter().map(|argumento| argumento.tipo_valor(&self.escopos)).collect();
+        let mut compativel = embutida.aceita(&tipos);
+
+        for (i, argumento) in argumentos.iter().enumerate() {
+            let referencia = embutida.referencias.contains(&i)
+                || (i >= parametros.len() && embutida.variadica == Variadica::Variaveis);
+            match argumento.identificador() {
+                Some(identificador) if referencia => self.verificar_atribuivel(identificador),
+                None if referencia => compativel = false,
+                _ => {}
+            }
+        }
+
+        if !compativel {
+            self.erro(Codigo::ParametrosIncompativeis, no.trecho(), format!("incompatibilidade de parametros na chamada de {}", embutida.nome));
+            return
+        }
+
+        if embutida.nome == "abra" {
+            let modo = argumentos[2].texto();
+            if modo.starts_with('"') && !biblioteca::MODOS_ABERTURA.contains(&modo.as_str()) {
+                self.erro(Codigo::ModoDeAbertura, no.trecho(), format!("modo de abertura {} invalido", modo));
+            }
+        }
+    }
+}
+
+/// forma como um identificador aparece no programa
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Uso {
+    Declaracao,
+    Atribuicao,
+    Leitura,
+    // argumentos de chamadas podem ser vetores inteiros ou parcialmente indexados
+    Argumento,
+}
+
+/// retorna a forma como aparece o identificador na posicao `posicao` dos filhos de `pai`, caso ela nao seja uma leitura
+fn uso_identificador(pai: &NoAST, posicao: usize) -> Option<Uso> {
+    let filhos = pai.filhos();
+    match (pai.regra(), posicao) {
+        // variavel : identificador identificadores ':' tipo inicializacao
+        // identificadores : ',' identificador identificadores | <<vazio>>
+        // parametro : var identificador identificadores ':' tipo_estendido
+        (RegraAST::Variavel | RegraAST::Identificadores, 0)
+        | (RegraAST::Parametro, 1) => Some(Uso::Declaracao),
+
+        // cmdAtribuicao : circunflexo identificador '<-' expressao
+        // cmdLeia : 'leia' '(' circunflexo identificador cmdLeia2 ')'
+        // cmdLeia2 : ',' circunflexo identificador cmdLeia2 | <<vazio>>
+        // em `^p <- x` e `leia(^p)` o ponteiro eh lido, e nao recebe valor
+        (RegraAST::CMDAtribuicao | RegraAST::CMDLeia | RegraAST::CMDLeia2, 1) if *filhos[0].regra() != RegraAST::Circunflexo => Some(Uso::Atribuicao),
+
+        _ => None
     }
 }
 
+/// retorna codigo e mensagem do erro relatado caso o filho na posicao `posicao` de `pai` seja uma condicao que nao eh logica
+fn condicao(pai: &NoAST, posicao: usize) -> Option<(Codigo, &'static str)> {
+    match (pai.regra(), posicao) {
+        // cmdSe : 'se' expressao 'entao' cmds senao_se 'fim_se'
+        // senao_se : 'senao' 'se' expressao 'entao' cmds senao_se | senao
+        (RegraAST::CMDSe | RegraAST::SenaoSe, 0) => Some((Codigo::CondicaoNaoLogica, "condicao de se deve ser logica")),
+        // cmdEnquanto : 'enquanto' expressao 'faca' cmds 'fim_enquanto'
+        (RegraAST::CMDEnquanto, 0) => Some((Codigo::CondicaoNaoLogica, "condicao de enquanto deve ser logica")),
+        // cmdFaca : 'faca' cmds 'ate' expressao
+        (RegraAST::CMDFaca, 1) => Some((Codigo::CondicaoNaoLogica, "condicao de faca ate deve ser logica")),
+        // cmdAfirme : 'afirme' expressao
+        (RegraAST::CMDAfirme(_), 0) => Some((Codigo::AfirmeNaoLogico, "expressao de afirme deve ser logica")),
+        _ => None
+    }
+}
+
+/// verifica se um valor do tipo `origem` pode ser atribuido a uma variavel do tipo `destino`\
+/// inteiros e reais sao intercambiaveis, exceto entre apelidos distintos, e variaveis de tipo invalido ja foram reportadas
+fn compativel_atribuicao(destino: &TipoSimbolo, origem: &TipoSimbolo, tipos: &TabelaDeTipos) -> bool {
+    if let (TipoSimbolo::Apelido(id_destino), TipoSimbolo::Apelido(id_origem)) = (destino, origem) {
+        return id_destino == id_origem
+    }
+    let (destino, origem) = (tipos.resolver(destino.clone()), tipos.resolver(origem.clone()));
+    let numerico = |tipo: &TipoSimbolo| matches!(tipo, TipoSimbolo::Real | TipoSimbolo::Inteiro);
+    (numerico(&destino) && numerico(&origem)) || destino == origem || destino == TipoSimbolo::Invalido
+}
+
 impl Visitor for Semantico {
 
+    /// percorre os nos filhos recursivamente, verificando diretamente os identificadores que sao declarados ou recebem valores
+    fn traverse(&mut self, no: &NoAST) {
+        self.visit(no);
+        for (posicao, filho) in no.filhos().iter().enumerate() {
+            match uso_identificador(no, posicao) {
+                Some(uso) => {
+                    self.verificar_identificador(filho, uso);
+                    for neto in filho.filhos() {
+                        self.traverse(neto)
+                    }
+                }
+                None => match (condicao(no, posicao), no.regra(), posicao) {
+                    (Some((codigo, mensagem)), _, _) => self.verificar_condicao(filho, codigo, mensagem),
+                    // cmdChamada : IDENT '(' expressao expressoes ')'
+                    // parcela_unario : IDENT '(' expressao expressoes ')'
+                    (None, RegraAST::CMDChamada | RegraAST::ParcelaUnario2, 1) => self.percorrer_argumento(filho),
+                    (None, RegraAST::CMDChamada | RegraAST::ParcelaUnario2, 2) => self.percorrer_argumentos(filho),
+                    (None, _, _) => self.traverse(filho)
+                }
+            }
+        }
+    }
+
     /// verifica um no da arvore sintatica segundo seus requisitos semanticos especificos
     fn visit(&mut self, no: &NoAST) {
         let filhos = no.filhos();
-        let escopos = self.escopos.clone();
 
         match no.regra() {
 
+            // declaracoes : declaracao declaracoes | <<vazio>>
+            // declaracoes_locais : declaracao_local declaracoes_locais | <<vazio>>
+            RegraAST::Declaracoes
+            | RegraAST::DeclaracoesLocais => {
+                // campos de um registro podem apontar para registros declarados adiante, permitindo tipos mutuamente recursivos
+                self.registros_adiante = match filhos[0].regra() {
+                    RegraAST::DeclaracaoTipo(_) if matches!(filhos[0].filhos()[1].regra(), RegraAST::Registro(_)) => no.registros(),
+                    _ => vec![]
+                };
+            }
+
             // declaracao_local :
             //     'declare' variavel
             RegraAST::DeclaracaoVariavel => {
                 let variavel = &no.filhos()[0];
-                let var_idents = variavel.idents();
-                let var_tipo = variavel.tipo(&escopos);
-
-                let escopo_atual = self.escopos.escopo_atual();
-
-                for var_ident in var_idents {
+                let var_tipo = match variavel.filhos()[2].regra() {
+                    RegraAST::Registro(_) => self.escopos.registrar_registro(&variavel.filhos()[2], ""),
+                    RegraAST::Enumeracao(_) => self.escopos.registrar_enumeracao(&variavel.filhos()[2], ""),
+                    _ => variavel.tipo(&self.escopos)
+                };
+
+                for identificador in variavel.identificadores() {
+                    let var_ident = identificador.filhos()[0].token().unwrap();
                     let nome = var_ident.lexema();
+                    let tamanhos = identificador.tamanhos(&self.escopos);
+                    let escopo_atual = self.escopos.escopo_atual();
                     if escopo_atual.existe(&nome) {
-                        let mensagem = format!("Linha {}: identificador {} ja declarado anteriormente\n", var_ident.linha(), nome);
-                        self.erros.push(mensagem);
+                        self.erro_ja_declarado(&var_ident);
                     } else {
-                        escopo_atual.inserir(&nome, &var_tipo);
-
-                        if let TipoSimbolo::Registro(ref atributos) = var_tipo {
-                            for atributo in atributos {
-                                let tipo = atributo.tipo(&escopos);
-                                let idents = atributo.idents();
-                                for ident in idents {
-                                    let nome = format!("{}.{}", nome, ident.lexema());
-                                    escopo_atual.inserir(&nome, &tipo)
-                                }
-                            }
-                        }
+                        escopo_atual.declarar_vetor(&nome, &var_tipo, Categoria::Variavel, var_ident.trecho(), tamanhos);
+                        self.declarar_campos(&nome, &var_tipo, var_ident.trecho());
+                    }
+                }
+
+                // inicializacao : '<-' valor_inicial
+                let inicializacao = &variavel.filhos()[3];
+                if *inicializacao.regra() != RegraAST::Vazio {
+                    for identificador in variavel.identificadores() {
+                        self.verificar_inicializacao(identificador, &var_tipo, &inicializacao.filhos()[0]);
                     }
                 }
             }
 
             //     | 'tipo' IDENT ':' tipo
-            RegraAST::DeclaracaoTipo => {
+            RegraAST::DeclaracaoTipo(_) => {
                 let ident = filhos[0].token().unwrap();
                 let nome = ident.lexema();
+                let tipo = self.escopos.registrar_tipo(no);
 
                 let escopo_atual = self.escopos.escopo_atual();
 
                 if escopo_atual.existe(&nome) {
-                    let mensagem = format!("Linha {}: identificador {} ja declarado anteriormente\n", ident.linha(), nome);
-                    self.erros.push(mensagem);
+                    self.erro_ja_declarado(&ident);
                 } else {
-                    escopo_atual.inserir(&nome, &filhos[1].tipo(&escopos));
+                    escopo_atual.declarar(&nome, &tipo, Categoria::Tipo, ident.trecho());
                 }
             }
 
@@ -89,57 +536,70 @@ impl Visitor for Semantico {
             RegraAST::DeclaracaoConstante => {
                 let ident = filhos[0].token().unwrap();
                 let nome = ident.lexema();
+                let mut tipo = filhos[1].tipo(&self.escopos);
+                if let TipoSimbolo::Vazio = tipo {
+                    tipo = TipoSimbolo::Inteiro
+                }
 
                 let escopo_atual = self.escopos.escopo_atual();
 
                 if escopo_atual.existe(&nome) {
-                    let mensagem = format!("Linha {}: identificador {} ja declarado anteriormente\n", ident.linha(), nome);
-                    self.erros.push(mensagem);
+                    self.erro_ja_declarado(&ident);
                 } else {
-                    let mut tipo = filhos[1].tipo(&escopos);
-                    if let TipoSimbolo::Vazio = tipo {
-                        tipo = TipoSimbolo::Inteiro
-                    }
-                    escopo_atual.inserir(&nome, &tipo)
+                    let valor = if tipo == TipoSimbolo::Inteiro { filhos[2].texto().parse().ok() } else { None };
+                    escopo_atual.declarar_constante(&nome, &tipo, ident.trecho(), valor)
                 }
             }
 
             // identificador : IDENT identificador2 dimensao
-            RegraAST::Identificador => {
-                let nome = filhos[0].texto() + &filhos[1].texto();
-
-                if !self.escopos.existe(&nome) {
-                    let ident = no.filhos()[0].token().unwrap();
-                    let mensagem = format!("Linha {}: identificador {} nao declarado\n", ident.linha(), nome);
-                    self.erros.push(mensagem);
-                }
-            }
+            RegraAST::Identificador => self.verificar_identificador(no, Uso::Leitura),
 
             // tipo_estendido : circunflexo tipo_basico_ident
             RegraAST::TipoExtendido => {
                 if let RegraAST::Ident(ident) = filhos[1].regra() {
                     let nome = ident.lexema();
-                    if !self.escopos.existe(&nome) {
-                        let mensagem = format!("Linha {}: tipo {} nao declarado\n", ident.linha(), nome);
-                        self.erros.push(mensagem);
+                    let adiante = *filhos[0].regra() == RegraAST::Circunflexo && self.registros_adiante.contains(&nome);
+                    if !self.escopos.existe(&nome) && !adiante {
+                        self.erro(Codigo::TipoNaoDeclarado, ident.trecho(), format!("tipo {} nao declarado", nome));
                     }
+                    self.usar(&nome, true);
                 }
 
             }
 
             // registro : 'registro' variaveis 'fim_Registro' fecha_escopo
-            RegraAST::Registro => {
+            RegraAST::Registro(_) => {
+                // tipos dos campos sao resolvidos no escopo em que o registro eh declarado
+                let atributos: Vec<(&NoAST, TipoSimbolo)> = no.atributos().into_iter()
+                    .map(|atributo| (atributo, atributo.tipo(&self.escopos)))
+                    .collect();
+
                 let tipo_retorno = TipoSimbolo::Vazio;
                 self.escopos.novo_escopo(tipo_retorno);
-                let escopo_atual = self.escopos.escopo_atual();
 
-                if let TipoSimbolo::Registro(atributos) = no.tipo(&escopos) {
-                    for atributo in atributos {
-                        let tipo = atributo.tipo(&escopos);
-                        let idents = atributo.idents();
-                        for ident in idents {
-                            escopo_atual.inserir(&ident.lexema(), &tipo);
-                        } 
+                for (atributo, tipo) in atributos {
+                    let idents = atributo.idents();
+                    let inicializado = *atributo.filhos()[3].regra() != RegraAST::Vazio;
+                    for ident in idents {
+                        if inicializado {
+                            self.erro(Codigo::CampoInicializado, ident.trecho(), format!("campo {} nao pode ser inicializado", ident.lexema()));
+                        }
+                        self.escopos.escopo_atual().declarar(&ident.lexema(), &tipo, Categoria::Campo, ident.trecho());
+                    }
+                }
+            }
+
+            // enumeracao : '(' IDENT valores_enumeracao ')'
+            RegraAST::Enumeracao(_) => {
+                let tipo = no.tipo(&self.escopos);
+                let valores = filhos[0].idents().into_iter().chain(filhos[1].idents());
+
+                for valor in valores {
+                    let nome = valor.lexema();
+                    if self.escopos.escopo_atual().existe(&nome) {
+                        self.erro_ja_declarado(&valor);
+                    } else {
+                        self.escopos.escopo_atual().declarar(&nome, &tipo, Categoria::ValorEnumerado, valor.trecho());
                     }
                 }
             }
@@ -150,38 +610,31 @@ impl Visitor for Semantico {
                 let ident = filhos[0].token().unwrap();
                 let nome = ident.lexema();
                 
+                let tipo = no.tipo(&self.escopos);
                 let escopo_externo = self.escopos.escopo_atual();
                 
                 if escopo_externo.existe(&nome) {
-                    let mensagem = format!("Linha {}: identificador {} ja declarado anteriormente\n", ident.linha(), nome);
-                    self.erros.push(mensagem);
+                    self.erro_ja_declarado(&ident);
                 } else {
-                    escopo_externo.inserir(&ident.lexema(), &no.tipo(&escopos));
+                    escopo_externo.declarar(&ident.lexema(), &tipo, Categoria::Procedimento, ident.trecho());
                     
                     let tipo_retorno = TipoSimbolo::Vazio;
                     self.escopos.novo_escopo(tipo_retorno);
                     
-                    if let TipoSimbolo::Procedimento(parametros) = no.tipo(&escopos) {
-                        let escopo_interno = self.escopos.escopo_atual();
-                        for parametro in parametros {
-                            let tipo = parametro.tipo(&escopos);
-                            let param_idents = parametro.idents();
-                            for param_ident in param_idents {
-                                if let TipoSimbolo::Registro(ref atributos) = tipo {
-                                    for atributo in atributos {
-                                        let tipo = atributo.tipo(&escopos);
-                                        let atr_idents = atributo.idents();
-                                        for atr_ident in atr_idents {
-                                            let nome = format!("{}.{}", param_ident.lexema(), atr_ident.lexema());
-                                            escopo_interno.inserir(&nome, &tipo)
-                                        }
-                                    }
-                                }
-                                escopo_interno.inserir(&param_ident.lexema(), &tipo);
-                            } 
+                    for parametro in no.parametros() {
+                        let tipo = parametro.tipo(&self.escopos);
+                        for identificador in parametro.identificadores() {
+                            let param_ident = identificador.filhos()[0].token().unwrap();
+                            let tamanhos = identificador.tamanhos(&self.escopos);
+                            let escopo_interno = self.escopos.escopo_atual();
+                            escopo_interno.declarar_vetor(&param_ident.lexema(), &tipo, Categoria::Parametro, param_ident.trecho(), tamanhos);
+                            self.declarar_campos(&param_ident.lexema(), &tipo, param_ident.trecho());
                         }
                     }
                 }
+
+                self.diagnosticos.extend(AtribuicaoDefinida::analisar(&filhos[2], &filhos[3], &self.escopos));
+                self.diagnosticos.extend(fluxo::inalcancaveis(&filhos[3], &self.escopos));
             }
 
             //     | 'funcao' IDENT '(' parametros ')' ':' tipo_estendido declaracoes_locais cmds 'fim_funcao' fecha_escopo
@@ -189,46 +642,51 @@ impl Visitor for Semantico {
                 let ident = filhos[0].token().unwrap();
                 let nome = ident.lexema();
 
+                let tipo = no.tipo(&self.escopos);
                 let escopo_externo = self.escopos.escopo_atual();
                 
                 if escopo_externo.existe(&nome) {
-                    let mensagem = format!("Linha {}: identificador {} ja declarado anteriormente\n", ident.linha(), nome);
-                    self.erros.push(mensagem);
+                    self.erro_ja_declarado(&ident);
                 } else {
-                    escopo_externo.inserir(&ident.lexema(), &no.tipo(&escopos));
+                    escopo_externo.declarar(&ident.lexema(), &tipo, Categoria::Funcao, ident.trecho());
                     
-                    let tipo_retorno = filhos[2].tipo(&escopos);
+                    let tipo_retorno = filhos[2].tipo(&self.escopos);
                     self.escopos.novo_escopo(tipo_retorno);
                     
-                    if let TipoSimbolo::Funcao { parametros, retorno: _ } = no.tipo(&escopos) {
-                        let escopo_interno = self.escopos.escopo_atual();
-                        for parametro in parametros {
-                            let tipo = parametro.tipo(&escopos);
-                            let param_idents = parametro.idents();
-                            for param_ident in param_idents {
-                                if let TipoSimbolo::Registro(ref atributos) = tipo {
-                                    for atributo in atributos {
-                                        let tipo = atributo.tipo(&escopos);
-                                        let atr_idents = atributo.idents();
-                                        for atr_ident in atr_idents {
-                                            let nome = format!("{}.{}", param_ident.lexema(), atr_ident.lexema());
-                                            escopo_interno.inserir(&nome, &tipo)
-                                        }
-                                    }
-                                }
-                                escopo_interno.inserir(&param_ident.lexema(), &tipo);
-                            } 
+                    for parametro in no.parametros() {
+                        let tipo = parametro.tipo(&self.escopos);
+                        for identificador in parametro.identificadores() {
+                            let param_ident = identificador.filhos()[0].token().unwrap();
+                            let tamanhos = identificador.tamanhos(&self.escopos);
+                            let escopo_interno = self.escopos.escopo_atual();
+                            escopo_interno.declarar_vetor(&param_ident.lexema(), &tipo, Categoria::Parametro, param_ident.trecho(), tamanhos);
+                            self.declarar_campos(&param_ident.lexema(), &tipo, param_ident.trecho());
                         }
                     }
                 }
+
+                self.diagnosticos.extend(AtribuicaoDefinida::analisar(&filhos[3], &filhos[4], &self.escopos));
+                self.diagnosticos.extend(fluxo::inalcancaveis(&filhos[4], &self.escopos));
+
+                if !fluxo::sempre_retorna(&filhos[4], &self.escopos) {
+                    let diagnostico = Diagnostico::erro(Codigo::RetornoAusente, ident.trecho(), format!("funcao {} pode terminar sem retornar um valor", nome))
+                        .com_sugestao(format!("termine a funcao {} com um comando retorne", nome));
+                    self.diagnosticos.push(diagnostico);
+                }
             }
             
+            // corpo : declaracoes_locais cmds
+            RegraAST::Corpo => {
+                self.diagnosticos.extend(AtribuicaoDefinida::analisar(&filhos[0], &filhos[1], &self.escopos));
+                self.diagnosticos.extend(fluxo::inalcancaveis(&filhos[1], &self.escopos));
+            }
+
             // cmdAtribuicao : circunflexo identificador '<-' expressao
             RegraAST::CMDAtribuicao => {
                 let ident = &filhos[1];
                 let expressao = &filhos[2];
 
-                let mut tipo_ident = ident.tipo(&self.escopos);
+                let mut tipo_ident = ident.tipo_nominal(&self.escopos);
                 if let RegraAST::Circunflexo = filhos[0].regra() {
                     tipo_ident = if let TipoSimbolo::Ponteiro(tipo) = tipo_ident {
                         *tipo
@@ -236,57 +694,166 @@ impl Visitor for Semantico {
                         TipoSimbolo::Invalido
                     }
                 }
-                let tipo_exp = expressao.tipo(&self.escopos);
+                let tipo_exp = expressao.tipo_nominal(&self.escopos);
                 
-                if (tipo_exp == TipoSimbolo::Real || tipo_exp == TipoSimbolo::Inteiro) && (tipo_ident == TipoSimbolo::Real || tipo_ident == TipoSimbolo::Inteiro) {
-                    
-                } else if tipo_exp != tipo_ident && tipo_ident != TipoSimbolo::Invalido {
+                // chamadas invalidas, e de procedimentos, destinos nao atribuiveis e tipos ou rotinas usados como valor
+                // ja relatam o proprio erro
+                let relatado = expressao.contem_chamada_invalida(&self.escopos) || expressao.contem_nao_valor(&self.escopos) || !self.atribuivel(ident);
+                if !compativel_atribuicao(&tipo_ident, &tipo_exp, self.escopos.tipos()) && !relatado {
                     let nome = filhos[0].texto() + &ident.texto();
                     
-                    let mensagem = format!("Linha {}: atribuicao nao compativel para {}\n", no.linha(), nome);
-                    self.erros.push(mensagem);
+                    self.erro(Codigo::AtribuicaoIncompativel, no.trecho(), format!("atribuicao nao compativel para {}", nome));
+                }
+            }
+
+            // escrita : expressao formato
+            // formato : ':' exp_aritmetica precisao
+            RegraAST::Escrita => {
+                if let RegraAST::Formato = filhos[1].regra() {
+                    let largura = &filhos[1].filhos()[0];
+                    let precisao = &filhos[1].filhos()[1];
+
+                    if largura.tipo(&self.escopos) != TipoSimbolo::Inteiro {
+                        self.erro(Codigo::FormatoInvalido, largura.trecho(), "largura de campo deve ser inteira".to_string());
+                    }
+                    if *precisao.regra() != RegraAST::Vazio {
+                        if filhos[0].tipo(&self.escopos) != TipoSimbolo::Real {
+                            self.erro(Codigo::FormatoInvalido, precisao.trecho(), "precisao so se aplica a valores reais".to_string());
+                        } else if precisao.tipo(&self.escopos) != TipoSimbolo::Inteiro {
+                            self.erro(Codigo::FormatoInvalido, precisao.trecho(), "precisao deve ser inteira".to_string());
+                        }
+                    }
                 }
             }
 
             // cmdRetorne : 'retorne' expressao
             RegraAST::CMDRetorne => {
-                if self.escopos.escopo_atual().tipo_retorno() == TipoSimbolo::Vazio {
-                    let mensagem = format!("Linha {}: comando retorne nao permitido nesse escopo\n", no.linha());
-                    self.erros.push(mensagem)
+                let tipo_retorno = self.escopos.escopo_atual().tipo_retorno();
+                let tipo_exp = filhos[0].tipo_nominal(&self.escopos);
+
+                if tipo_retorno == TipoSimbolo::Vazio {
+                    self.erro(Codigo::RetorneNaoPermitido, no.trecho(), "comando retorne nao permitido nesse escopo".to_string());
+                } else if !compativel_atribuicao(&tipo_retorno, &tipo_exp, self.escopos.tipos()) && !filhos[0].contem_chamada_invalida(&self.escopos) {
+                    self.erro(Codigo::RetornoIncompativel, no.trecho(), "tipo da expressao de retorne incompativel com o retorno da funcao".to_string());
                 }
             }
             
             // tratando chamadas de funcoes
             //     | IDENT '(' expressao expressoes ')'
             RegraAST::ParcelaUnario2 => {
-                let params_chamada = no.variaveis(&escopos);
-                
-                if let TipoSimbolo::Funcao { parametros: params_funcao, retorno: _ } = no.tipo(&escopos) {
-                    if params_chamada.len() != params_funcao.len() {
-                        let mensagem = format!("Linha {}: incompatibilidade de parametros na chamada de {}\n",  no.linha(), no.filhos()[0].idents()[0].lexema());
-                        self.erros.push(mensagem);
-                        return
+                let nome = filhos[0].texto();
+                self.usar(&nome, true);
+
+                // o tipo da chamada eh invalido quando os argumentos sao incompativeis, entao a verificacao parte do nome chamado
+                match self.escopos.tipos().resolver(filhos[0].tipo(&self.escopos)) {
+                    TipoSimbolo::Funcao { parametros: _, retorno: _ } if self.escopos.embutido(&nome) => {
+                        if let Some(embutida) = no.embutida(&self.escopos) {
+                            self.verificar_embutida(no, embutida);
+                        }
                     }
-                    for (param_chamada, param_funcao) in params_chamada.iter().zip(params_funcao.iter()) {
-                        let tipo_chamada = if let TipoSimbolo::Funcao { parametros: _, retorno } = param_chamada.tipo(&escopos) {
-                            *retorno
-                        } else { param_chamada.tipo(&escopos) };
-                        
-                        if tipo_chamada != param_funcao.tipo(&escopos) {
-                            let mensagem = format!("Linha {}: incompatibilidade de parametros na chamada de {}\n",  no.linha(), no.filhos()[0].idents()[0].lexema());
-                            self.erros.push(mensagem);
-                            return
+                    TipoSimbolo::Funcao { parametros, retorno: _ } => self.verificar_argumentos(no, &parametros),
+                    TipoSimbolo::Enumeracao(_) => {
+                        // conversao explicita de inteiro para enumeracao, como em Cor(2)
+                        let argumentos = no.variaveis();
+                        if argumentos.len() != 1 || argumentos[0].tipo(&self.escopos) != TipoSimbolo::Inteiro {
+                            self.erro(Codigo::ParametrosIncompativeis, no.trecho(), format!("incompatibilidade de parametros na chamada de {}", nome));
                         }
                     }
+                    TipoSimbolo::Procedimento(_) => self.erro(Codigo::ProcedimentoEmExpressao, no.trecho(), format!("procedimento {} nao retorna valor", nome)),
+                    _ if !self.escopos.existe(&nome) => self.erro(Codigo::IdentificadorNaoDeclarado, no.trecho(), format!("identificador {} nao declarado", nome)),
+                    _ => {}
                 }
             }
             
+            // cmdChamada : IDENT '(' expressao expressoes ')'
+            RegraAST::CMDChamada => {
+                let nome = filhos[0].texto();
+                self.usar(&nome, true);
+                if self.escopos.embutido(&nome) {
+                    if let Some(embutida) = no.embutida(&self.escopos) {
+                        self.verificar_embutida(no, embutida);
+                    }
+                } else {
+                    match self.escopos.verificar(&nome).map(|simbolo| simbolo.tipo()) {
+                        Some(TipoSimbolo::Procedimento(parametros))
+                        | Some(TipoSimbolo::Funcao { parametros, retorno: _ }) => self.verificar_argumentos(no, &parametros),
+                        Some(_) => self.erro(Codigo::ChamadaInvalida, no.trecho(), format!("{} nao eh um procedimento", nome)),
+                        None => self.erro(Codigo::IdentificadorNaoDeclarado, no.trecho(), format!("identificador {} nao declarado", nome)),
+                    }
+                }
+
+                if self.estrito {
+                    if let Some(TipoSimbolo::Funcao { parametros: _, retorno: _ }) = self.escopos.verificar(&nome).map(|simbolo| simbolo.tipo()) {
+                        let diagnostico = Diagnostico::erro(Codigo::ResultadoDescartado, no.trecho(), format!("resultado da funcao {} descartado", nome))
+                            .com_sugestao(format!("atribua o resultado de {} a uma variavel", nome));
+                        self.diagnosticos.push(diagnostico);
+                    }
+                }
+            }
+
+            // numero_intervalo : op_unario NUM_INT numero_intervalo2
+            //     | IDENT ident_intervalo2
+            // ident_intervalo2 : '..' IDENT | <<vazio>>
+            RegraAST::NumeroIntervalo
+            | RegraAST::NumeroIntervalo2 => {
+                if let RegraAST::Ident(ident) = filhos[1].regra() {
+                    let nome = ident.lexema();
+                    self.usar(&nome, true);
+                    if !self.escopos.existe(&nome) {
+                        self.erro(Codigo::IdentificadorNaoDeclarado, ident.trecho(), format!("identificador {} nao declarado", nome));
+                    } else if !matches!(filhos[1].tipo(&self.escopos), TipoSimbolo::Enumeracao(_)) {
+                        self.erro(Codigo::ConstanteNaoEnumerada, ident.trecho(), format!("{} nao eh uma constante enumerada", nome));
+                    }
+                }
+            }
+
+            // cmdPara : 'para' IDENT '<-' exp_aritmetica 'ate' exp_aritmetica 'faca' cmds 'fim_para'
+            RegraAST::CMDPara => {
+                let ident = filhos[0].token().unwrap();
+                // a variavel de controle eh lida a cada iteracao
+                self.usar(&ident.lexema(), true);
+
+                if !self.escopos.existe(&ident.lexema()) {
+                    self.erro(Codigo::IdentificadorNaoDeclarado, ident.trecho(), format!("identificador {} nao declarado", ident.lexema()));
+                } else if self.escopos.tipos().resolver(filhos[0].tipo(&self.escopos)) != TipoSimbolo::Inteiro {
+                    self.erro(Codigo::ParaNaoInteiro, ident.trecho(), format!("variavel de controle {} deve ser inteira", ident.lexema()));
+                } else {
+                    // a variavel de controle recebe um valor a cada iteracao
+                    self.verificar_atribuivel(&filhos[0]);
+                }
+                for limite in [&filhos[1], &filhos[2]] {
+                    if !matches!(limite.tipo_valor(&self.escopos), TipoSimbolo::Inteiro | TipoSimbolo::Invalido) {
+                        self.erro(Codigo::ParaNaoInteiro, limite.trecho(), "limites de para devem ser inteiros".to_string());
+                    }
+                }
+            }
+
+            // exp_relacional : exp_aritmetica exp_relacional2
+            // exp_relacional2 : op_relacional exp_aritmetica | <<vazio>>
+            RegraAST::ExpRelacional if *filhos[1].regra() != RegraAST::Vazio => {
+                // operandos de tipo invalido ja foram relatados ou serao relatados pelo comando que os usa
+                let validos = [&filhos[0], &filhos[1]].iter().all(|operando| operando.tipo_valor(&self.escopos) != TipoSimbolo::Invalido);
+                if validos && !no.comparaveis(&self.escopos) {
+                    self.erro(Codigo::ComparacaoIncompativel, no.trecho(), "comparacao entre valores de tipos incompativeis".to_string());
+                }
+            }
+
+            // cmdCaso : 'caso' exp_aritmetica 'seja' selecao senao 'fim_caso'
+            // alem de inteiros, o seletor pode ser um caractere ou um valor enumerado
+            RegraAST::CMDCaso => {
+                if !matches!(filhos[0].tipo_valor(&self.escopos), TipoSimbolo::Inteiro | TipoSimbolo::Caractere | TipoSimbolo::Enumeracao(_) | TipoSimbolo::Invalido) {
+                    self.erro(Codigo::SeletorInvalido, filhos[0].trecho(), "seletor de caso deve ser inteiro".to_string());
+                }
+                self.verificar_rotulos(no);
+            }
+
             RegraAST::FechaEscopo => {
+                self.avisar_nao_usados();
                 self.escopos.abandonar_escopo()
             }
 
-            RegraAST::Erro { mensagem } => {
-                self.erros.push(mensagem.to_string());
+            RegraAST::Erro { diagnostico } => {
+                self.diagnosticos.push(*diagnostico.clone());
             }
 
             _ => {}
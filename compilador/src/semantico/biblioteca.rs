@@ -0,0 +1,389 @@
+use crate::diagnostico::Trecho;
+use crate::lexico::token::TipoToken;
+
+use super::tabela_de_simbolos::{Categoria, TabelaDeSimbolos, TipoSimbolo};
+use super::tipos::Parametro;
+
+/// funcao embutida da linguagem LA, disponivel em qualquer programa sem declaracao previa
+pub struct Embutida {
+    /// nome da funcao em LA
+    pub nome: &'static str,
+    /// tipos dos parametros, representados pelas palavras chave de tipo_basico\
+    /// `PCtipo` representa um parametro que aceita qualquer tipo enumerado ou caractere
+    pub parametros: &'static [TipoToken],
+    /// tipo de retorno, representado pela palavra chave de tipo_basico\
+    /// `Vazio` indica um procedimento
+    pub retorno: TipoToken,
+    /// nome da funcao equivalente em C
+    pub nome_c: &'static str,
+    /// definicao em C da funcao equivalente, caso ela nao exista na biblioteca padrao
+    pub definicao: &'static str,
+    /// cabecalhos C necessarios alem de stdio.h, stdlib.h e string.h
+    pub cabecalhos: &'static [&'static str],
+    /// tratamento dos argumentos alem dos parametros declarados
+    pub variadica: Variadica,
+    /// posicoes dos parametros que recebem variaveis, pois sao modificados pela chamada
+    pub referencias: &'static [usize],
+}
+
+/// tratamento dos argumentos excedentes de uma funcao embutida
+#[derive(PartialEq)]
+pub enum Variadica {
+    /// nenhum argumento alem dos parametros declarados
+    Nao,
+    /// qualquer quantidade de expressoes de tipos basicos, como em `escreva`
+    Valores,
+    /// qualquer quantidade de variaveis de tipos basicos, como em `leia`
+    Variaveis,
+}
+
+/// buffers rotativos usados pelas funcoes que retornam literais, permitindo chamadas aninhadas
+pub const BUFFER_LITERAL: &str = "
+char *la_buffer(void) {
+	static char buffers[16][80];
+	static int atual = 0;
+	atual = (atual + 1) % 16;
+	return buffers[atual];
+}";
+
+/// funcoes embutidas da linguagem LA
+pub const EMBUTIDAS: &[Embutida] = &[
+    Embutida {
+        nome: "comprimento",
+        parametros: &[TipoToken::PCliteral],
+        retorno: TipoToken::PCinteiro,
+        nome_c: "(int) strlen",
+        definicao: "",
+        cabecalhos: &[],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "concatene",
+        parametros: &[TipoToken::PCliteral, TipoToken::PCliteral],
+        retorno: TipoToken::PCliteral,
+        nome_c: "la_concatene",
+        definicao: "
+char *la_concatene(char *a, char *b) {
+	char *resultado = la_buffer();
+	snprintf(resultado, 80, \"%s%s\", a, b);
+	return resultado;
+}",
+        cabecalhos: &[],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "subcadeia",
+        parametros: &[TipoToken::PCliteral, TipoToken::PCinteiro, TipoToken::PCinteiro],
+        retorno: TipoToken::PCliteral,
+        nome_c: "la_subcadeia",
+        definicao: "
+char *la_subcadeia(char *s, int inicio, int tamanho) {
+	char *resultado = la_buffer();
+	int n = strlen(s);
+	if (inicio < 0) inicio = 0;
+	if (inicio > n) inicio = n;
+	if (tamanho < 0) tamanho = 0;
+	snprintf(resultado, 80, \"%.*s\", tamanho, s + inicio);
+	return resultado;
+}",
+        cabecalhos: &[],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "maiusculo",
+        parametros: &[TipoToken::PCliteral],
+        retorno: TipoToken::PCliteral,
+        nome_c: "la_maiusculo",
+        definicao: "
+char *la_maiusculo(char *s) {
+	char *resultado = la_buffer();
+	int i;
+	for (i = 0; s[i] != '\\0' && i < 79; i++) {
+		resultado[i] = toupper((unsigned char) s[i]);
+	}
+	resultado[i] = '\\0';
+	return resultado;
+}",
+        cabecalhos: &["ctype.h"],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "raiz",
+        parametros: &[TipoToken::PCreal],
+        retorno: TipoToken::PCreal,
+        nome_c: "sqrt",
+        definicao: "",
+        cabecalhos: &["math.h"],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "potencia",
+        parametros: &[TipoToken::PCreal, TipoToken::PCreal],
+        retorno: TipoToken::PCreal,
+        nome_c: "pow",
+        definicao: "",
+        cabecalhos: &["math.h"],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    // abs tem uma versao para inteiros e outra para reais, e o resultado segue o tipo do argumento
+    Embutida {
+        nome: "abs",
+        parametros: &[TipoToken::PCinteiro],
+        retorno: TipoToken::PCinteiro,
+        nome_c: "abs",
+        definicao: "",
+        cabecalhos: &[],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "abs",
+        parametros: &[TipoToken::PCreal],
+        retorno: TipoToken::PCreal,
+        nome_c: "fabs",
+        definicao: "",
+        cabecalhos: &["math.h"],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "arredonda",
+        parametros: &[TipoToken::PCreal],
+        retorno: TipoToken::PCinteiro,
+        nome_c: "(int) round",
+        definicao: "",
+        cabecalhos: &["math.h"],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "trunca",
+        parametros: &[TipoToken::PCreal],
+        retorno: TipoToken::PCinteiro,
+        nome_c: "(int) trunc",
+        definicao: "",
+        cabecalhos: &["math.h"],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "aleatorio",
+        parametros: &[TipoToken::PCinteiro],
+        retorno: TipoToken::PCinteiro,
+        nome_c: "la_aleatorio",
+        definicao: "
+int la_aleatorio(int n) {
+	static int semeado = 0;
+	if (!semeado) {
+		srand(time(NULL));
+		semeado = 1;
+	}
+	return n > 0 ? rand() % n : 0;
+}",
+        cabecalhos: &["time.h"],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "ordem",
+        parametros: &[TipoToken::PCtipo],
+        retorno: TipoToken::PCinteiro,
+        nome_c: "(int) ",
+        definicao: "",
+        cabecalhos: &[],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "caractere_de",
+        parametros: &[TipoToken::PCinteiro],
+        retorno: TipoToken::PCcaractere,
+        nome_c: "(char) ",
+        definicao: "",
+        cabecalhos: &[],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "abra",
+        parametros: &[TipoToken::PCarquivo, TipoToken::PCliteral, TipoToken::PCliteral],
+        retorno: TipoToken::Vazio,
+        nome_c: "la_abra",
+        definicao: "
+FILE *la_abra(char *nome, char *modo) {
+	FILE *arquivo = fopen(nome, strcmp(modo, \"escrita\") == 0 ? \"w\" : strcmp(modo, \"acrescimo\") == 0 ? \"a\" : \"r\");
+	if (arquivo == NULL) {
+		fprintf(stderr, \"nao foi possivel abrir o arquivo %s\\n\", nome);
+		exit(1);
+	}
+	return arquivo;
+}",
+        cabecalhos: &[],
+        variadica: Variadica::Nao,
+        referencias: &[0],
+    },
+    Embutida {
+        nome: "leia_arquivo",
+        parametros: &[TipoToken::PCarquivo],
+        retorno: TipoToken::Vazio,
+        nome_c: "fscanf",
+        definicao: "",
+        cabecalhos: &[],
+        variadica: Variadica::Variaveis,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "escreva_arquivo",
+        parametros: &[TipoToken::PCarquivo],
+        retorno: TipoToken::Vazio,
+        nome_c: "fprintf",
+        definicao: "",
+        cabecalhos: &[],
+        variadica: Variadica::Valores,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "fim_de_arquivo",
+        parametros: &[TipoToken::PCarquivo],
+        retorno: TipoToken::PClogico,
+        nome_c: "la_fim_de_arquivo",
+        definicao: "
+int la_fim_de_arquivo(FILE *arquivo) {
+	int c;
+	do {
+		c = fgetc(arquivo);
+	} while (c != EOF && isspace(c));
+	if (c == EOF) return 1;
+	ungetc(c, arquivo);
+	return 0;
+}",
+        cabecalhos: &["ctype.h"],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+    Embutida {
+        nome: "feche",
+        parametros: &[TipoToken::PCarquivo],
+        retorno: TipoToken::Vazio,
+        nome_c: "fclose",
+        definicao: "",
+        cabecalhos: &[],
+        variadica: Variadica::Nao,
+        referencias: &[],
+    },
+];
+
+/// retorna funcao embutida com nome `nome`, caso exista
+pub fn buscar(nome: &str) -> Option<&'static Embutida> {
+    EMBUTIDAS.iter().find(|embutida| embutida.nome == nome)
+}
+
+/// retorna versao da funcao embutida `nome` que aceita argumentos dos tipos `argumentos`,
+/// ou sua primeira versao caso nenhuma os aceite
+pub fn escolher(nome: &str, argumentos: &[TipoSimbolo]) -> Option<&'static Embutida> {
+    EMBUTIDAS.iter()
+        .find(|embutida| embutida.nome == nome && embutida.aceita(argumentos))
+        .or_else(|| buscar(nome))
+}
+
+/// verifica se um argumento do tipo `argumento` pode ser passado a um parametro do tipo `parametro` de uma funcao embutida\
+/// inteiros sao aceitos onde se espera real, como em C, e o parametro `PCtipo` aceita qualquer enumeracao ou caractere
+pub fn compativel(parametro: &TipoSimbolo, argumento: &TipoSimbolo) -> bool {
+    match (parametro, argumento) {
+        (TipoSimbolo::Real, TipoSimbolo::Inteiro) => true,
+        (TipoSimbolo::Vazio, TipoSimbolo::Enumeracao(_) | TipoSimbolo::Caractere) => true,
+        _ => parametro == argumento
+    }
+}
+
+/// modos aceitos pela funcao embutida `abra`
+pub const MODOS_ABERTURA: &[&str] = &["\"leitura\"", "\"escrita\"", "\"acrescimo\""];
+
+/// verifica se valores do tipo `tipo` podem ser lidos e escritos por funcoes embutidas variadicas\
+/// valores logicos nao possuem formato de leitura e escrita em C, e por isso nao sao aceitos
+pub fn basico(tipo: &TipoSimbolo) -> bool {
+    matches!(tipo,
+        TipoSimbolo::Cadeia
+        | TipoSimbolo::Inteiro
+        | TipoSimbolo::Real
+        | TipoSimbolo::Caractere
+        | TipoSimbolo::Enumeracao(_))
+}
+
+/// insere todas as funcoes embutidas em `escopo`
+pub fn registrar(escopo: &mut TabelaDeSimbolos) {
+    // funcoes com mais de uma versao sao declaradas uma unica vez
+    for embutida in EMBUTIDAS {
+        if escopo.existe(embutida.nome) {
+            continue
+        }
+        let categoria = if embutida.retorno == TipoToken::Vazio { Categoria::Procedimento } else { Categoria::Funcao };
+        escopo.declarar(embutida.nome, &embutida.tipo(), categoria, Trecho::default());
+    }
+}
+
+impl Embutida {
+
+    /// retorna tipo da funcao embutida, no mesmo formato de uma funcao ou procedimento declarado pelo usuario
+    pub fn tipo(&self) -> TipoSimbolo {
+        let parametros = self.parametros.iter()
+            .enumerate()
+            .map(|(i, tipo)| Parametro {
+                nome: "".to_string(),
+                tipo: tipo_simbolo(*tipo),
+                tamanhos: vec![],
+                referencia: self.referencias.contains(&i),
+            })
+            .collect();
+
+        if self.retorno == TipoToken::Vazio {
+            TipoSimbolo::Procedimento(parametros)
+        } else {
+            TipoSimbolo::Funcao {
+                parametros,
+                retorno: Box::new(tipo_simbolo(self.retorno)),
+            }
+        }
+    }
+
+    /// verifica quantidade e tipos dos argumentos de uma chamada, cujos tipos sao `argumentos`, sem considerar o modo de passagem
+    pub fn aceita(&self, argumentos: &[TipoSimbolo]) -> bool {
+        let parametros = self.tipos_parametros();
+        let quantidade = argumentos.len() == parametros.len()
+            || (self.variadica != Variadica::Nao && argumentos.len() > parametros.len());
+
+        quantidade && argumentos.iter().enumerate().all(|(i, tipo)| match parametros.get(i) {
+            Some(parametro) => compativel(parametro, tipo),
+            None => basico(tipo),
+        })
+    }
+
+    /// retorna tipos dos parametros, na ordem em que sao declarados
+    pub fn tipos_parametros(&self) -> Vec<TipoSimbolo> {
+        self.parametros.iter().map(|tipo| tipo_simbolo(*tipo)).collect()
+    }
+
+    /// verifica se a funcao retorna um literal, e portanto depende de `BUFFER_LITERAL`
+    pub fn retorna_literal(&self) -> bool {
+        self.retorno == TipoToken::PCliteral
+    }
+}
+
+/// retorna tipo de simbolo correspondente a palavra chave de tipo_basico `tipo`
+fn tipo_simbolo(tipo: TipoToken) -> TipoSimbolo {
+    match tipo {
+        TipoToken::PCliteral => TipoSimbolo::Cadeia,
+        TipoToken::PCinteiro => TipoSimbolo::Inteiro,
+        TipoToken::PCreal => TipoSimbolo::Real,
+        TipoToken::PClogico => TipoSimbolo::Logico,
+        TipoToken::PCarquivo => TipoSimbolo::Arquivo,
+        TipoToken::PCcaractere => TipoSimbolo::Caractere,
+        _ => TipoSimbolo::Vazio,
+    }
+}
@@ -1,11 +1,17 @@
-use crate::semantico::tabela_de_simbolos::{Simbolo, TabelaDeSimbolos};
+use crate::semantico::{
+    biblioteca,
+    tabela_de_simbolos::{Simbolo, TabelaDeSimbolos},
+    tipos::{Campo, TabelaDeTipos},
+};
+use crate::sintatico::arvore_sintatica::{NoAST, RegraAST};
 
 use super::tabela_de_simbolos::TipoSimbolo;
 
-/// vetor de tabelas de simbolos, cada uma representando um escopo
+/// vetor de tabelas de simbolos, cada uma representando um escopo, e tabela dos tipos declarados
 #[derive(Debug, Clone)]
 pub struct Escopos {
     tabelas: Vec<TabelaDeSimbolos>,
+    tipos: TabelaDeTipos,
 }
 
 impl Escopos {
@@ -14,11 +20,29 @@ impl Escopos {
     pub fn new(retorno: TipoSimbolo) -> Self {
         let mut escopos = Self {
             tabelas: vec![],
+            tipos: TabelaDeTipos::new(),
         };
         escopos.novo_escopo(retorno);
         escopos
     }
 
+    /// retorna instancia de escopos cujo escopo mais externo contem as funcoes embutidas\
+    /// as declaracoes globais do programa ficam em um escopo acima dele, podendo redeclarar seus nomes
+    pub fn com_biblioteca() -> Self {
+        let mut escopos = Self::new(TipoSimbolo::Vazio);
+        biblioteca::registrar(escopos.escopo_atual());
+        escopos.novo_escopo(TipoSimbolo::Vazio);
+        escopos
+    }
+
+    /// retorna instancia de escopos com as funcoes embutidas e os tipos ja registrados em `tipos`
+    pub fn com_tipos(tipos: TabelaDeTipos) -> Self {
+        Self {
+            tipos,
+            ..Self::com_biblioteca()
+        }
+    }
+
     /// adiciona escopo
     pub fn novo_escopo(&mut self, retorno: TipoSimbolo) {
         self.tabelas.insert(0, TabelaDeSimbolos::new(retorno))
@@ -29,24 +53,19 @@ impl Escopos {
         &mut self.tabelas[0]
     }
 
-    /// retorna todos os escopos atualmente existentes
-    pub fn tabelas(&self) -> Vec<TabelaDeSimbolos> {
-        self.tabelas.clone()
-    }
-
     /// remove escopo mais recente
     pub fn abandonar_escopo(&mut self) {
         self.tabelas.remove(0);
     }
 
     /// retorna primeiro simbolo com identificador `nome`, caso exista em algum escopo
-    pub fn verificar(&self, nome: &str) -> Option<Simbolo> {
-        for escopo in &self.tabelas {
-            if escopo.existe(nome) {
-                return escopo.verificar(nome)
-            }
-        }
-        None
+    pub fn verificar(&self, nome: &str) -> Option<&Simbolo> {
+        self.tabelas.iter().find(|escopo| escopo.existe(nome))?.verificar(nome)
+    }
+
+    /// retorna referencia mutavel para o primeiro simbolo com identificador `nome`, caso exista em algum escopo
+    pub fn verificar_mut(&mut self, nome: &str) -> Option<&mut Simbolo> {
+        self.tabelas.iter_mut().find(|escopo| escopo.existe(nome))?.verificar_mut(nome)
     }
 
     /// verifica se identificador `nome` existe em algum escopo
@@ -56,4 +75,101 @@ impl Escopos {
         }
         false
     }
-}
\ No newline at end of file
+
+    /// verifica se identificador `nome` se refere a um simbolo do escopo mais externo,
+    /// onde ficam as funcoes embutidas, sem ter sido redeclarado em um escopo interno
+    pub fn embutido(&self, nome: &str) -> bool {
+        match self.tabelas.iter().position(|escopo| escopo.existe(nome)) {
+            Some(indice) => indice == self.tabelas.len() - 1,
+            None => false
+        }
+    }
+
+    /// retorna tabela dos tipos declarados
+    pub fn tipos(&self) -> &TabelaDeTipos {
+        &self.tipos
+    }
+
+    /// retorna tipo declarado por `declaracao`, uma declaracao 'tipo'\
+    /// cada declaracao eh registrada como um tipo distinto, e registros copiados de outro registro recebem seus campos
+    pub fn registrar_tipo(&mut self, declaracao: &NoAST) -> TipoSimbolo {
+        if let Some(tipo) = self.tipos.declarado(Self::numero(declaracao)) {
+            return tipo
+        }
+
+        let filhos = declaracao.filhos();
+        let nome = filhos[0].texto();
+        let tipo = match filhos[1].regra() {
+            RegraAST::Registro(_) => self.registrar_registro(&filhos[1], &nome),
+            RegraAST::Enumeracao(_) => self.registrar_enumeracao(&filhos[1], &nome),
+            _ => match filhos[1].tipo(self) {
+                TipoSimbolo::Registro(original) => {
+                    let id = self.tipos.reservar(Self::numero(declaracao), &nome);
+                    let campos = self.tipos.registro(original).campos.clone();
+                    self.tipos.definir(id, campos);
+                    TipoSimbolo::Registro(id)
+                }
+                base => self.tipos.apelidar(Self::numero(declaracao), &nome, base)
+            }
+        };
+        self.tipos.associar(Self::numero(declaracao), &tipo);
+        tipo
+    }
+
+    /// registra o registro declarado por `registro`, com nome `nome` caso seja declarado com 'tipo',
+    /// junto com os registros anonimos de seus campos, retornando seu tipo
+    pub fn registrar_registro(&mut self, registro: &NoAST, nome: &str) -> TipoSimbolo {
+        if let Some(tipo) = self.tipos.declarado(Self::numero(registro)) {
+            return tipo
+        }
+
+        let id = self.tipos.reservar(Self::numero(registro), nome);
+        let mut campos = vec![];
+        for atributo in registro.atributos() {
+            let tipo = self.tipo_campo(&atributo.filhos()[2]);
+            for identificador in atributo.identificadores() {
+                campos.push(Campo {
+                    nome: identificador.filhos()[0].texto(),
+                    tipo: tipo.clone(),
+                    tamanhos: identificador.tamanhos(self),
+                });
+            }
+        }
+        self.tipos.definir(id, campos);
+        TipoSimbolo::Registro(id)
+    }
+
+    /// registra a enumeracao declarada por `enumeracao`, com nome `nome` caso seja declarada com 'tipo', retornando seu tipo
+    pub fn registrar_enumeracao(&mut self, enumeracao: &NoAST, nome: &str) -> TipoSimbolo {
+        match self.tipos.declarado(Self::numero(enumeracao)) {
+            Some(tipo) => tipo,
+            None => self.tipos.enumerar(Self::numero(enumeracao), nome, enumeracao.valores())
+        }
+    }
+
+    /// retorna tipo de um campo declarado com o tipo `tipo`\
+    /// campos podem apontar para o proprio registro ou para registros declarados adiante
+    fn tipo_campo(&mut self, tipo: &NoAST) -> TipoSimbolo {
+        let filhos = tipo.filhos();
+        match tipo.regra() {
+            RegraAST::Registro(_) => self.registrar_registro(tipo, ""),
+            RegraAST::Enumeracao(_) => self.registrar_enumeracao(tipo, ""),
+            RegraAST::TipoExtendido if *filhos[0].regra() == RegraAST::Circunflexo
+                && matches!(filhos[1].regra(), RegraAST::Ident(_))
+                && !self.existe(&filhos[1].texto()) => {
+                TipoSimbolo::Ponteiro(Box::new(TipoSimbolo::Registro(self.tipos.adiante(&filhos[1].texto()))))
+            }
+            _ => tipo.tipo(self)
+        }
+    }
+
+    /// retorna numero atribuido pelo parser a uma declaracao de tipo, registro ou enumeracao
+    fn numero(declaracao: &NoAST) -> usize {
+        match declaracao.regra() {
+            RegraAST::DeclaracaoTipo(numero)
+            | RegraAST::Registro(numero)
+            | RegraAST::Enumeracao(numero) => *numero,
+            _ => 0
+        }
+    }
+}
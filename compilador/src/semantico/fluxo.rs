@@ -0,0 +1,410 @@
+use std::collections::HashSet;
+
+use crate::diagnostico::{Codigo, Diagnostico};
+use crate::sintatico::arvore_sintatica::{NoAST, RegraAST};
+
+use super::biblioteca::{self, Variadica};
+use super::escopos::Escopos;
+use super::tabela_de_simbolos::TipoSimbolo;
+
+/// analise de atribuicao definida sobre os comandos do corpo de um programa, procedimento ou funcao\
+/// acompanha, em cada ponto, quais variaveis locais receberam valor em todos os caminhos que levam ate ele
+pub struct AtribuicaoDefinida<'a> {
+    escopos: &'a Escopos,
+    // variaveis locais acompanhadas pela analise
+    locais: HashSet<String>,
+    // variaveis ja relatadas, para que cada uma gere um unico aviso
+    avisadas: HashSet<String>,
+    avisos: Vec<Diagnostico>,
+}
+
+impl<'a> AtribuicaoDefinida<'a> {
+
+    /// retorna avisos de leituras de variaveis declaradas em `declaracoes_locais` que podem ocorrer antes de elas receberem valor em `cmds`
+    pub fn analisar(declaracoes_locais: &NoAST, cmds: &NoAST, escopos: &'a Escopos) -> Vec<Diagnostico> {
+        let mut analise = Self {
+            escopos,
+            locais: HashSet::new(),
+            avisadas: HashSet::new(),
+            avisos: vec![],
+        };
+
+        let mut atribuidas = HashSet::new();
+        analise.declarar(declaracoes_locais, &mut atribuidas);
+        analise.cmds(cmds, atribuidas);
+        analise.avisos
+    }
+
+    /// registra variaveis de uma sequencia de declaracoes locais, considerando atribuidas as que possuem valor inicial\
+    /// vetores nao sao acompanhados, ja que costumam receber valores elemento a elemento dentro de lacos
+    fn declarar(&mut self, declaracoes: &NoAST, atribuidas: &mut HashSet<String>) {
+        match declaracoes.regra() {
+            RegraAST::DeclaracoesLocais => {
+                for filho in declaracoes.filhos() {
+                    self.declarar(filho, atribuidas)
+                }
+            }
+            RegraAST::DeclaracaoVariavel => {
+                let variavel = &declaracoes.filhos()[0];
+                let inicializada = *variavel.filhos()[3].regra() != RegraAST::Vazio;
+                for identificador in variavel.identificadores() {
+                    if !identificador.dimensoes().is_empty() {
+                        continue
+                    }
+                    let nome = identificador.filhos()[0].texto();
+                    if inicializada {
+                        atribuidas.insert(nome.clone());
+                    }
+                    self.locais.insert(nome);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// analisa uma sequencia de comandos, retornando as variaveis atribuidas ao seu final
+    fn cmds(&mut self, cmds: &NoAST, mut atribuidas: HashSet<String>) -> HashSet<String> {
+        let mut cmds = cmds;
+        while let RegraAST::CMDs = cmds.regra() {
+            atribuidas = self.cmd(&cmds.filhos()[0], atribuidas);
+            cmds = &cmds.filhos()[1];
+        }
+        atribuidas
+    }
+
+    /// analisa um comando, retornando as variaveis atribuidas apos sua execucao
+    fn cmd(&mut self, cmd: &NoAST, mut atribuidas: HashSet<String>) -> HashSet<String> {
+        let filhos = cmd.filhos();
+
+        match cmd.regra() {
+
+            // cmdAtribuicao : circunflexo identificador '<-' expressao
+            RegraAST::CMDAtribuicao => {
+                self.ler(&filhos[2], &mut atribuidas);
+                self.atribuir(&filhos[0], &filhos[1], &mut atribuidas);
+            }
+
+            // cmdLeia : 'leia' '(' circunflexo identificador cmdLeia2 ')'
+            // cmdLeia2 : ',' circunflexo identificador cmdLeia2 | <<vazio>>
+            RegraAST::CMDLeia
+            | RegraAST::CMDLeia2 => {
+                self.atribuir(&filhos[0], &filhos[1], &mut atribuidas);
+                return self.cmd(&filhos[2], atribuidas)
+            }
+
+            // cmdSe : 'se' expressao 'entao' cmds senao_se 'fim_se'
+            RegraAST::CMDSe => {
+                self.ler(&filhos[0], &mut atribuidas);
+                let entao = self.cmds(&filhos[1], atribuidas.clone());
+                let senao = self.senao(&filhos[2], atribuidas);
+                return &entao & &senao
+            }
+
+            // cmdCaso : 'caso' exp_aritmetica 'seja' selecao senao 'fim_caso'
+            // selecao : item_selecao selecao | <<vazio>>
+            // item_selecao : constantes ':' cmds
+            RegraAST::CMDCaso => {
+                self.ler(&filhos[0], &mut atribuidas);
+                let mut resultado = self.senao(&filhos[2], atribuidas.clone());
+                let mut selecao = &filhos[1];
+                while let RegraAST::Selecao = selecao.regra() {
+                    let item = self.cmds(&selecao.filhos()[0].filhos()[1], atribuidas.clone());
+                    resultado = &resultado & &item;
+                    selecao = &selecao.filhos()[1];
+                }
+                return resultado
+            }
+
+            // cmdPara : 'para' IDENT '<-' exp_aritmetica 'ate' exp_aritmetica 'faca' cmds 'fim_para'
+            RegraAST::CMDPara => {
+                self.ler(&filhos[1], &mut atribuidas);
+                self.ler(&filhos[2], &mut atribuidas);
+                atribuidas.insert(filhos[0].texto());
+
+                let corpo = self.cmds(&filhos[3], atribuidas.clone());
+                if para_executa_ao_menos_uma_vez(cmd, self.escopos) == Some(true) {
+                    return corpo
+                }
+            }
+
+            // cmdEnquanto : 'enquanto' expressao 'faca' cmds 'fim_enquanto'
+            RegraAST::CMDEnquanto => {
+                self.ler(&filhos[0], &mut atribuidas);
+                self.cmds(&filhos[1], atribuidas.clone());
+            }
+
+            // cmdFaca : 'faca' cmds 'ate' expressao
+            RegraAST::CMDFaca => {
+                atribuidas = self.cmds(&filhos[0], atribuidas);
+                self.ler(&filhos[1], &mut atribuidas);
+            }
+
+            // cmdChamada : IDENT '(' expressao expressoes ')'
+            RegraAST::CMDChamada => self.chamada(cmd, &mut atribuidas),
+
+            _ => self.ler(cmd, &mut atribuidas)
+        }
+
+        atribuidas
+    }
+
+    /// analisa alternativas de um comando se ou caso, retornando as variaveis atribuidas em todas elas\
+    /// sem 'senao', o caminho em que nenhuma alternativa eh executada mantem as variaveis atribuidas antes do comando
+    fn senao(&mut self, senao: &NoAST, mut atribuidas: HashSet<String>) -> HashSet<String> {
+        let filhos = senao.filhos();
+
+        match senao.regra() {
+            // senao_se : 'senao' 'se' expressao 'entao' cmds senao_se | senao
+            RegraAST::SenaoSe => {
+                self.ler(&filhos[0], &mut atribuidas);
+                let entao = self.cmds(&filhos[1], atribuidas.clone());
+                let senao = self.senao(&filhos[2], atribuidas);
+                &entao & &senao
+            }
+            // senao : 'senao' cmds | <<vazio>>
+            RegraAST::Senao => self.cmds(&filhos[0], atribuidas),
+            _ => atribuidas
+        }
+    }
+
+    /// registra atribuicao a `identificador`\
+    /// em `^p <- x` e `p^.campo <- x` o ponteiro eh lido, e nao recebe valor
+    fn atribuir(&mut self, circunflexo: &NoAST, identificador: &NoAST, atribuidas: &mut HashSet<String>) {
+        if *circunflexo.regra() == RegraAST::Circunflexo || identificador.filhos()[1].texto().contains('^') {
+            self.ler(identificador, atribuidas);
+        } else {
+            self.ler(&identificador.filhos()[2], atribuidas);
+            atribuidas.insert(identificador.filhos()[0].texto());
+        }
+    }
+
+    /// verifica leituras de variaveis em uma expressao
+    fn ler(&mut self, no: &NoAST, atribuidas: &mut HashSet<String>) {
+        match no.regra() {
+            // identificador : IDENT identificador2 dimensao
+            RegraAST::Identificador => {
+                let nome = no.filhos()[0].texto();
+                if self.locais.contains(&nome) && !atribuidas.contains(&nome) && self.avisadas.insert(nome.clone()) {
+                    self.avisos.push(Diagnostico::aviso(Codigo::LeituraSemValor, no.trecho(), format!("variavel {} pode ser lida antes de receber um valor", nome)));
+                }
+                self.ler(&no.filhos()[2], atribuidas);
+            }
+
+            // parcela_nao_unario : '&' identificador
+            RegraAST::ParcelaNaoUnario => {
+                // o endereco permite que a variavel receba valor por meio do ponteiro
+                let identificador = &no.filhos()[0];
+                self.ler(&identificador.filhos()[2], atribuidas);
+                atribuidas.insert(identificador.filhos()[0].texto());
+            }
+
+            //     | IDENT '(' expressao expressoes ')'
+            RegraAST::ParcelaUnario2 => self.chamada(no, atribuidas),
+
+            _ => {
+                for filho in no.filhos() {
+                    self.ler(filho, atribuidas)
+                }
+            }
+        }
+    }
+
+    /// analisa argumentos de uma chamada, em que variaveis passadas por referencia recebem valor
+    fn chamada(&mut self, chamada: &NoAST, atribuidas: &mut HashSet<String>) {
+        let nome = chamada.filhos()[0].texto();
+        // argumentos sao retornados em ordem inversa
+        let mut argumentos = chamada.variaveis();
+        argumentos.reverse();
+
+        let mut referencias = vec![];
+        for (i, argumento) in argumentos.iter().enumerate() {
+            match argumento.identificador() {
+                Some(identificador) if self.referencia(&nome, i) => {
+                    self.ler(&identificador.filhos()[2], atribuidas);
+                    referencias.push(identificador.filhos()[0].texto());
+                }
+                _ => self.ler(argumento, atribuidas)
+            }
+        }
+        atribuidas.extend(referencias);
+    }
+
+    /// verifica se o argumento na posicao `posicao` de uma chamada a `nome` eh passado por referencia
+    fn referencia(&self, nome: &str, posicao: usize) -> bool {
+        if self.escopos.embutido(nome) {
+            return biblioteca::buscar(nome).is_some_and(|embutida| {
+                embutida.referencias.contains(&posicao)
+                    || (posicao >= embutida.parametros.len() && embutida.variadica == Variadica::Variaveis)
+            })
+        }
+
+        match self.escopos.verificar(nome).map(|simbolo| simbolo.tipo()) {
+            Some(TipoSimbolo::Procedimento(parametros))
+            | Some(TipoSimbolo::Funcao { parametros, retorno: _ }) => parametros.get(posicao).is_some_and(|parametro| parametro.referencia),
+            _ => false
+        }
+    }
+}
+
+/// verifica se o corpo de um comando para eh executado ao menos uma vez, caso seus limites sejam constantes\
+/// limites formados por numeros ou constantes inteiras sao conhecidos durante a compilacao
+fn para_executa_ao_menos_uma_vez(para: &NoAST, escopos: &Escopos) -> Option<bool> {
+    // cmdPara : 'para' IDENT '<-' exp_aritmetica 'ate' exp_aritmetica 'faca' cmds 'fim_para'
+    let inicio = para.filhos()[1].valor_inteiro(escopos)?;
+    let fim = para.filhos()[2].valor_inteiro(escopos)?;
+    Some(inicio <= fim)
+}
+
+/// verifica se toda execucao de uma sequencia de comandos termina em 'retorne'\
+/// lacos 'enquanto verdadeiro' e 'faca ... ate verdadeiro' nunca terminam, e por isso tambem nao chegam ao fim da sequencia
+pub fn sempre_retorna(cmds: &NoAST, escopos: &Escopos) -> bool {
+    let mut cmds = cmds;
+    while let RegraAST::CMDs = cmds.regra() {
+        if cmd_sempre_retorna(&cmds.filhos()[0], escopos) {
+            return true
+        }
+        cmds = &cmds.filhos()[1];
+    }
+    false
+}
+
+/// verifica se toda execucao de um comando termina em 'retorne'
+fn cmd_sempre_retorna(cmd: &NoAST, escopos: &Escopos) -> bool {
+    let filhos = cmd.filhos();
+
+    match cmd.regra() {
+        // cmdRetorne : 'retorne' expressao
+        RegraAST::CMDRetorne => true,
+
+        // cmdSe : 'se' expressao 'entao' cmds senao_se 'fim_se'
+        // senao_se : 'senao' 'se' expressao 'entao' cmds senao_se | senao
+        RegraAST::CMDSe
+        | RegraAST::SenaoSe => sempre_retorna(&filhos[1], escopos) && cmd_sempre_retorna(&filhos[2], escopos),
+
+        // senao : 'senao' cmds | <<vazio>>
+        RegraAST::Senao => sempre_retorna(&filhos[0], escopos),
+
+        // cmdCaso : 'caso' exp_aritmetica 'seja' selecao senao 'fim_caso'
+        // selecao : item_selecao selecao | <<vazio>>
+        RegraAST::CMDCaso => {
+            let mut retorna = cmd_sempre_retorna(&filhos[2], escopos);
+            let mut selecao = &filhos[1];
+            while let RegraAST::Selecao = selecao.regra() {
+                retorna &= sempre_retorna(&selecao.filhos()[0].filhos()[1], escopos);
+                selecao = &selecao.filhos()[1];
+            }
+            retorna
+        }
+
+        // cmdPara : 'para' IDENT '<-' exp_aritmetica 'ate' exp_aritmetica 'faca' cmds 'fim_para'
+        RegraAST::CMDPara => para_executa_ao_menos_uma_vez(cmd, escopos) == Some(true) && sempre_retorna(&filhos[3], escopos),
+
+        // cmdEnquanto : 'enquanto' expressao 'faca' cmds 'fim_enquanto'
+        RegraAST::CMDEnquanto => filhos[0].constante_logica() == Some(true),
+
+        // cmdFaca : 'faca' cmds 'ate' expressao
+        // o corpo se repete enquanto a expressao for verdadeira
+        RegraAST::CMDFaca => sempre_retorna(&filhos[0], escopos) || filhos[1].constante_logica() == Some(true),
+
+        _ => false
+    }
+}
+
+/// retorna avisos de comandos que nunca sao executados em uma sequencia de comandos\
+/// cada trecho inalcancavel eh relatado uma unica vez, na linha de seu primeiro comando
+pub fn inalcancaveis(cmds: &NoAST, escopos: &Escopos) -> Vec<Diagnostico> {
+    let mut avisos = vec![];
+    avisar_inalcancaveis(cmds, escopos, &mut avisos);
+    avisos
+}
+
+/// registra aviso para uma sequencia de comandos inalcancavel, caso ela nao seja vazia
+fn avisar_inalcancavel(cmds: &NoAST, avisos: &mut Vec<Diagnostico>) {
+    if let RegraAST::CMDs = cmds.regra() {
+        avisos.push(Diagnostico::aviso(Codigo::ComandoInalcancavel, cmds.trecho(), "comando inalcancavel".to_string()));
+    }
+}
+
+/// procura comandos inalcancaveis em uma sequencia de comandos e nos comandos aninhados nela
+fn avisar_inalcancaveis(cmds: &NoAST, escopos: &Escopos, avisos: &mut Vec<Diagnostico>) {
+    let mut cmds = cmds;
+    while let RegraAST::CMDs = cmds.regra() {
+        let cmd = &cmds.filhos()[0];
+        avisar_inalcancaveis_cmd(cmd, escopos, avisos);
+
+        // comandos apos um que nunca chega ao fim, como 'retorne', nao sao executados
+        if cmd_sempre_retorna(cmd, escopos) {
+            avisar_inalcancavel(&cmds.filhos()[1], avisos);
+            return
+        }
+        cmds = &cmds.filhos()[1];
+    }
+}
+
+/// procura comandos inalcancaveis nos blocos de um comando, considerando condicoes constantes
+fn avisar_inalcancaveis_cmd(cmd: &NoAST, escopos: &Escopos, avisos: &mut Vec<Diagnostico>) {
+    let filhos = cmd.filhos();
+
+    match cmd.regra() {
+        // cmdSe : 'se' expressao 'entao' cmds senao_se 'fim_se'
+        // senao_se : 'senao' 'se' expressao 'entao' cmds senao_se | senao
+        RegraAST::CMDSe
+        | RegraAST::SenaoSe => {
+            match filhos[0].constante_logica() {
+                Some(true) => {
+                    avisar_inalcancaveis(&filhos[1], escopos, avisos);
+                    match filhos[2].regra() {
+                        RegraAST::SenaoSe => avisos.push(Diagnostico::aviso(Codigo::ComandoInalcancavel, filhos[2].trecho(), "comando inalcancavel".to_string())),
+                        RegraAST::Senao => avisar_inalcancavel(&filhos[2].filhos()[0], avisos),
+                        _ => {}
+                    }
+                }
+                Some(false) => {
+                    avisar_inalcancavel(&filhos[1], avisos);
+                    avisar_inalcancaveis_cmd(&filhos[2], escopos, avisos);
+                }
+                None => {
+                    avisar_inalcancaveis(&filhos[1], escopos, avisos);
+                    avisar_inalcancaveis_cmd(&filhos[2], escopos, avisos);
+                }
+            }
+        }
+
+        // senao : 'senao' cmds | <<vazio>>
+        RegraAST::Senao => avisar_inalcancaveis(&filhos[0], escopos, avisos),
+
+        // cmdCaso : 'caso' exp_aritmetica 'seja' selecao senao 'fim_caso'
+        // selecao : item_selecao selecao | <<vazio>>
+        RegraAST::CMDCaso => {
+            let mut selecao = &filhos[1];
+            while let RegraAST::Selecao = selecao.regra() {
+                avisar_inalcancaveis(&selecao.filhos()[0].filhos()[1], escopos, avisos);
+                selecao = &selecao.filhos()[1];
+            }
+            avisar_inalcancaveis_cmd(&filhos[2], escopos, avisos);
+        }
+
+        // cmdPara : 'para' IDENT '<-' exp_aritmetica 'ate' exp_aritmetica 'faca' cmds 'fim_para'
+        RegraAST::CMDPara => {
+            if para_executa_ao_menos_uma_vez(cmd, escopos) == Some(false) {
+                avisar_inalcancavel(&filhos[3], avisos);
+            } else {
+                avisar_inalcancaveis(&filhos[3], escopos, avisos);
+            }
+        }
+
+        // cmdEnquanto : 'enquanto' expressao 'faca' cmds 'fim_enquanto'
+        RegraAST::CMDEnquanto => {
+            if filhos[0].constante_logica() == Some(false) {
+                avisar_inalcancavel(&filhos[1], avisos);
+            } else {
+                avisar_inalcancaveis(&filhos[1], escopos, avisos);
+            }
+        }
+
+        // cmdFaca : 'faca' cmds 'ate' expressao
+        RegraAST::CMDFaca => avisar_inalcancaveis(&filhos[0], escopos, avisos),
+
+        _ => {}
+    }
+}
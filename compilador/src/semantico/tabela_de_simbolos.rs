@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::sintatico::arvore_sintatica::NoAST;
+use crate::diagnostico::Trecho;
+
+use super::tipos::{Parametro, TipoId};
 
 /// representa o tipo de um simbolo da linguagem LA
 #[derive(Debug, Clone, PartialEq)]
@@ -9,22 +11,48 @@ pub enum TipoSimbolo {
     Real,
     Inteiro,
     Logico,
-    Registro(Vec<NoAST>),
+    Arquivo,
+    Caractere,
+    // registros, enumeracoes e apelidos sao identificados pela tabela de tipos, e cada declaracao eh um tipo distinto
+    Registro(TipoId),
+    Enumeracao(TipoId),
+    // apelidos sao distintos entre si, mas compativeis com seu tipo base
+    Apelido(TipoId),
     Funcao {
-        parametros: Vec<NoAST>,
+        parametros: Vec<Parametro>,
         retorno: Box<TipoSimbolo>,
     },
-    Procedimento(Vec<NoAST>),
+    Procedimento(Vec<Parametro>),
     Ponteiro(Box<TipoSimbolo>),
     Vazio,
     Invalido,
 }
 
+/// representa o que um simbolo da linguagem LA nomeia
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Categoria {
+    Variavel,
+    Parametro,
+    Campo,
+    Constante,
+    ValorEnumerado,
+    Tipo,
+    Procedimento,
+    Funcao,
+}
+
 /// representa um simbolo da linguagem LA
 #[derive(Debug, Clone, PartialEq)]
 pub struct Simbolo {
     nome: String,
     tipo: TipoSimbolo,
+    // tamanho de cada dimensao, quando conhecido durante a compilacao
+    tamanhos: Vec<Option<i32>>,
+    // valor de constantes inteiras
+    valor: Option<i32>,
+    categoria: Categoria,
+    trecho: Trecho,
+    usado: bool,
 }
 
 impl Simbolo {
@@ -34,6 +62,11 @@ impl Simbolo {
         Self {
             nome,
             tipo,
+            tamanhos: vec![],
+            valor: None,
+            categoria: Categoria::Variavel,
+            trecho: Trecho::default(),
+            usado: false,
         }
     }
 
@@ -46,6 +79,46 @@ impl Simbolo {
     pub fn tipo(&self) -> TipoSimbolo {
         self.tipo.clone()
     }
+
+    /// retorna quantidade de dimensoes com que o simbolo foi declarado, 0 caso nao seja um vetor
+    pub fn dimensoes(&self) -> usize {
+        self.tamanhos.len()
+    }
+
+    /// retorna tamanho de cada dimensao com que o simbolo foi declarado, `None` nas que nao sao constantes
+    pub fn tamanhos(&self) -> Vec<Option<i32>> {
+        self.tamanhos.clone()
+    }
+
+    /// retorna valor do simbolo, caso seja uma constante inteira
+    pub fn valor(&self) -> Option<i32> {
+        self.valor
+    }
+
+    /// retorna o que o simbolo nomeia
+    pub fn categoria(&self) -> Categoria {
+        self.categoria
+    }
+
+    /// retorna linha em que o simbolo foi declarado, 0 caso nao tenha sido declarado pelo programa
+    pub fn linha(&self) -> u32 {
+        self.trecho.linha
+    }
+
+    /// retorna trecho em que o simbolo foi declarado
+    pub fn trecho(&self) -> Trecho {
+        self.trecho
+    }
+
+    /// verifica se o simbolo foi usado apos sua declaracao
+    pub fn usado(&self) -> bool {
+        self.usado
+    }
+
+    /// registra que o simbolo foi usado
+    pub fn marcar_uso(&mut self) {
+        self.usado = true;
+    }
 }
 
 /// representa um escopo
@@ -74,9 +147,50 @@ impl TabelaDeSimbolos {
         self.tabela.insert(nome.to_string(), Simbolo::new(nome.to_string(), tipo.clone()));
     }
 
-    /// retorna simbolo, caso exista nesse escopo
-    pub fn verificar(&self, nome: &str) -> Option<Simbolo> {
-        self.tabela.get(nome).cloned()
+    /// insere simbolo declarado como vetor de `dimensoes` dimensoes
+    pub fn inserir_vetor(&mut self, nome: &str, tipo: &TipoSimbolo, dimensoes: usize) {
+        let mut simbolo = Simbolo::new(nome.to_string(), tipo.clone());
+        simbolo.tamanhos = vec![None; dimensoes];
+        self.tabela.insert(nome.to_string(), simbolo);
+    }
+
+    /// insere simbolo declarado pelo programa em `trecho`
+    pub fn declarar(&mut self, nome: &str, tipo: &TipoSimbolo, categoria: Categoria, trecho: Trecho) {
+        self.declarar_vetor(nome, tipo, categoria, trecho, vec![]);
+    }
+
+    /// insere simbolo declarado pelo programa em `trecho` como vetor com dimensoes de tamanhos `tamanhos`
+    pub fn declarar_vetor(&mut self, nome: &str, tipo: &TipoSimbolo, categoria: Categoria, trecho: Trecho, tamanhos: Vec<Option<i32>>) {
+        let mut simbolo = Simbolo::new(nome.to_string(), tipo.clone());
+        simbolo.tamanhos = tamanhos;
+        simbolo.categoria = categoria;
+        simbolo.trecho = trecho;
+        self.tabela.insert(nome.to_string(), simbolo);
+    }
+
+    /// insere constante declarada pelo programa em `trecho`, guardando seu valor caso seja inteiro
+    pub fn declarar_constante(&mut self, nome: &str, tipo: &TipoSimbolo, trecho: Trecho, valor: Option<i32>) {
+        self.declarar(nome, tipo, Categoria::Constante, trecho);
+        if let Some(simbolo) = self.tabela.get_mut(nome) {
+            simbolo.valor = valor;
+        }
+    }
+
+    /// retorna simbolos do escopo, na ordem em que foram declarados
+    pub fn simbolos(&self) -> Vec<Simbolo> {
+        let mut simbolos: Vec<Simbolo> = self.tabela.values().cloned().collect();
+        simbolos.sort_by(|a, b| (a.trecho, &a.nome).cmp(&(b.trecho, &b.nome)));
+        simbolos
+    }
+
+    /// retorna referencia mutavel para simbolo, caso exista nesse escopo
+    pub fn verificar_mut(&mut self, nome: &str) -> Option<&mut Simbolo> {
+        self.tabela.get_mut(nome)
+    }
+
+    /// retorna referencia para simbolo, caso exista nesse escopo
+    pub fn verificar(&self, nome: &str) -> Option<&Simbolo> {
+        self.tabela.get(nome)
     }
 
     /// verifica se simbolo com identificador `nome` existe nesse escopo
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use super::tabela_de_simbolos::TipoSimbolo;
+
+/// identificador de um registro, enumeracao ou apelido na tabela de tipos
+pub type TipoId = usize;
+
+/// representa um campo de registro, com seu tipo ja resolvido
+#[derive(Debug, Clone, PartialEq)]
+pub struct Campo {
+    pub nome: String,
+    pub tipo: TipoSimbolo,
+    // tamanho de cada dimensao, quando conhecido durante a compilacao
+    pub tamanhos: Vec<Option<i32>>,
+}
+
+/// representa um parametro de funcao ou procedimento, com seu tipo ja resolvido
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parametro {
+    pub nome: String,
+    pub tipo: TipoSimbolo,
+    pub tamanhos: Vec<Option<i32>>,
+    // parametros 'var' sao passados por referencia
+    pub referencia: bool,
+}
+
+/// representa um registro, com nome vazio caso seja anonimo
+#[derive(Debug, Clone, PartialEq)]
+pub struct Registro {
+    pub nome: String,
+    pub campos: Vec<Campo>,
+}
+
+/// representa uma enumeracao, com nome vazio caso seja anonima
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enumeracao {
+    pub nome: String,
+    // valores na ordem em que foram declarados
+    pub valores: Vec<String>,
+}
+
+/// representa um tipo declarado com 'tipo' como outro nome para um tipo que nao eh registro nem enumeracao
+#[derive(Debug, Clone, PartialEq)]
+pub struct Apelido {
+    pub nome: String,
+    pub base: TipoSimbolo,
+}
+
+/// tabela dos tipos declarados pelo programa\
+/// cada declaracao recebe seu proprio identificador, e so eh equivalente a si mesma
+#[derive(Debug, Clone, Default)]
+pub struct TabelaDeTipos {
+    registros: Vec<Registro>,
+    enumeracoes: Vec<Enumeracao>,
+    apelidos: Vec<Apelido>,
+    // tipo criado por cada declaracao, indexado pelo numero atribuido a ela pelo parser
+    declaracoes: HashMap<usize, TipoSimbolo>,
+    // registros cujos campos ainda estao sendo resolvidos
+    em_definicao: Vec<TipoId>,
+    // registros apontados por campos antes de serem declarados
+    adiante: Vec<TipoId>,
+}
+
+impl TabelaDeTipos {
+
+    /// retorna nova tabela de tipos
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// retorna registro com identificador `id`
+    pub fn registro(&self, id: TipoId) -> &Registro {
+        &self.registros[id]
+    }
+
+    /// retorna enumeracao com identificador `id`
+    pub fn enumeracao(&self, id: TipoId) -> &Enumeracao {
+        &self.enumeracoes[id]
+    }
+
+    /// retorna tipo criado pela declaracao de numero `declaracao`, caso ja tenha sido registrada
+    pub fn declarado(&self, declaracao: usize) -> Option<TipoSimbolo> {
+        self.declaracoes.get(&declaracao).cloned()
+    }
+
+    /// retorna campos de `tipo`, vazio caso nao seja um registro
+    pub fn campos(&self, tipo: &TipoSimbolo) -> Vec<Campo> {
+        match tipo {
+            TipoSimbolo::Registro(id) => self.registros[*id].campos.clone(),
+            _ => vec![]
+        }
+    }
+
+    /// retorna tipo base de `tipo`, que so difere dele quando `tipo` eh um apelido
+    pub fn resolver(&self, tipo: TipoSimbolo) -> TipoSimbolo {
+        match tipo {
+            TipoSimbolo::Apelido(id) => self.apelidos[id].base.clone(),
+            tipo => tipo
+        }
+    }
+
+    /// verifica se valores do tipo `origem` podem ser usados onde se espera o tipo `destino`\
+    /// apelidos distintos nunca sao equivalentes, mas um apelido eh equivalente ao seu tipo base,
+    /// para que possa receber literais e resultados de operacoes
+    pub fn equivalentes(&self, destino: &TipoSimbolo, origem: &TipoSimbolo) -> bool {
+        match (destino, origem) {
+            (TipoSimbolo::Apelido(id_destino), TipoSimbolo::Apelido(id_origem)) => id_destino == id_origem,
+            _ => self.resolver(destino.clone()) == self.resolver(origem.clone())
+        }
+    }
+
+    /// associa `tipo` a declaracao de numero `declaracao`
+    pub fn associar(&mut self, declaracao: usize, tipo: &TipoSimbolo) {
+        self.declaracoes.insert(declaracao, tipo.clone());
+    }
+
+    /// reserva identificador para o registro da declaracao de numero `declaracao`, cujos campos ainda serao resolvidos\
+    /// um registro nomeado ja apontado por campos anteriores reaproveita o identificador reservado para ele
+    pub fn reservar(&mut self, declaracao: usize, nome: &str) -> TipoId {
+        let id = match self.adiante.iter().rposition(|id| !nome.is_empty() && self.registros[*id].nome == nome) {
+            Some(indice) => self.adiante.remove(indice),
+            None => self.novo(nome),
+        };
+        self.associar(declaracao, &TipoSimbolo::Registro(id));
+        self.em_definicao.push(id);
+        id
+    }
+
+    /// define campos do registro `id`, concluindo sua declaracao
+    pub fn definir(&mut self, id: TipoId, campos: Vec<Campo>) {
+        self.registros[id].campos = campos;
+        self.em_definicao.retain(|outro| *outro != id);
+    }
+
+    /// registra a enumeracao da declaracao de numero `declaracao`, retornando seu tipo
+    pub fn enumerar(&mut self, declaracao: usize, nome: &str, valores: Vec<String>) -> TipoSimbolo {
+        self.enumeracoes.push(Enumeracao { nome: nome.to_string(), valores });
+        let tipo = TipoSimbolo::Enumeracao(self.enumeracoes.len() - 1);
+        self.associar(declaracao, &tipo);
+        tipo
+    }
+
+    /// registra o apelido `nome` da declaracao de numero `declaracao` para o tipo `base`, retornando seu tipo\
+    /// apelidos de apelidos tem como base o tipo base do apelido original
+    pub fn apelidar(&mut self, declaracao: usize, nome: &str, base: TipoSimbolo) -> TipoSimbolo {
+        let base = self.resolver(base);
+        self.apelidos.push(Apelido { nome: nome.to_string(), base });
+        let tipo = TipoSimbolo::Apelido(self.apelidos.len() - 1);
+        self.associar(declaracao, &tipo);
+        tipo
+    }
+
+    /// retorna identificador do registro `nome` apontado por um campo antes de ser declarado,
+    /// que pode ser o proprio registro em definicao ou um registro declarado adiante
+    pub fn adiante(&mut self, nome: &str) -> TipoId {
+        let pendente = self.em_definicao.iter()
+            .chain(&self.adiante)
+            .rev()
+            .find(|id| self.registros[**id].nome == nome);
+        match pendente {
+            Some(id) => *id,
+            None => {
+                let id = self.novo(nome);
+                self.adiante.push(id);
+                id
+            }
+        }
+    }
+
+    /// adiciona registro sem campos
+    fn novo(&mut self, nome: &str) -> TipoId {
+        self.registros.push(Registro { nome: nome.to_string(), campos: vec![] });
+        self.registros.len() - 1
+    }
+}
@@ -1,15 +1,16 @@
 use std::vec;
 
 use crate::{
+    diagnostico::{Diagnostico, Trecho},
     lexico::token::{TipoToken, Token},
-    semantico::{escopos::Escopos, tabela_de_simbolos::TipoSimbolo}
+    semantico::{biblioteca::{self, Embutida}, escopos::Escopos, tabela_de_simbolos::{Categoria, TipoSimbolo}, tipos::Parametro}
 };
 
 /// regra da gramatica que cada no da arvore sintatica representa
 #[derive(Debug, Clone, PartialEq)]
 pub enum RegraAST {
 
-    // programa : declaracoes 'algoritmo' corpo 'fim_algoritmo'
+    // programa : declaracoes 'algoritmo' corpo 'fim_algoritmo' fecha_escopo
     Programa,
     
     // declaracoes : declaracao declaracoes | <<vazio>>
@@ -21,24 +22,35 @@ pub enum RegraAST {
     DeclaracaoVariavel,
     
     //     | 'tipo' IDENT ':' tipo
-    DeclaracaoTipo,
+    // declaracoes de tipo, registros e enumeracoes sao numerados pelo parser, identificando-os na tabela de tipos
+    DeclaracaoTipo (usize),
     
     //     | 'constante' IDENT ':' tipo_basico '=' valor_constante
     DeclaracaoConstante,
     
-    // valor_constante : CADEIA | NUM_INT | NUM_REAL | 'verdadeiro' | 'falso'
+    // valor_constante : CADEIA | CARACTERE | NUM_INT | NUM_REAL | 'verdadeiro' | 'falso'
     ValorConstante (Token),
     
-    // variavel : identificador identificadores ':' tipo
+    // variavel : identificador identificadores ':' tipo inicializacao
     Variavel,
-    // tipo : registro | tipo_estendido
+
+    // inicializacao : '<-' valor_inicial | <<vazio>>
+    Inicializacao,
+
+    // valor_inicial : expressao
+    //     | '[' expressao expressoes ']'
+    ValoresIniciais,
+    // tipo : registro | enumeracao | tipo_estendido
     
     // identificador : IDENT identificador2 dimensao
     Identificador,
 
-    // identificador2 : '.' IDENT identificador2 | <<vazio>>
+    // identificador2 : '.' IDENT identificador2
     Identificador2,
 
+    //     | '^' '.' IDENT identificador2
+    AcessoPonteiro,
+
     // identificadores: ',' identificador identificadores | <<vazio>>
     Identificadores,
 
@@ -51,18 +63,24 @@ pub enum RegraAST {
     //    | IDENT
     Ident (Token),
 
-    // tipo_basico : 'literal' | 'inteiro' | 'real' | 'logico'
+    // tipo_basico : 'literal' | 'inteiro' | 'real' | 'logico' | 'arquivo' | 'caractere'
     TipoBasico (Token),
     
     // circunflexo: '^' | <<vazio>>
     Circunflexo,
 
     // registro : 'registro' variaveis 'fim_Registro' fecha_escopo
-    Registro,
+    Registro (usize),
 
     // variaveis : variavel variaveis | <<vazio>>
     Variaveis,
 
+    // enumeracao : '(' IDENT valores_enumeracao ')'
+    Enumeracao (usize),
+
+    // valores_enumeracao : ',' IDENT valores_enumeracao | <<vazio>>
+    ValoresEnumeracao,
+
     // declaracao_global :
     //     'procedimento' IDENT '(' parametros ')' declaracoes_locais cmds 'fim_procedimento' fecha_escopo
     DeclaracaoProcedimento,
@@ -91,7 +109,7 @@ pub enum RegraAST {
     // cmds : cmd cmds | <<vazio>>
     CMDs,
     // cmd : cmdLeia | cmdEscreva | cmdSe | cmdCaso | cmdPara | cmdEnquanto
-    //     | cmdFaca | cmdAtribuicao | cmdChamada | cmdRetorne
+    //     | cmdFaca | cmdAtribuicao | cmdChamada | cmdRetorne | cmdAfirme
 
     // cmdLeia : 'leia' '(' circunflexo identificador cmdLeia2 ')'
     CMDLeia,
@@ -99,12 +117,25 @@ pub enum RegraAST {
     // cmdLeia2 : ',' circunflexo identificador cmdLeia2 | <<vazio>>
     CMDLeia2,
 
-    // cmdEscreva : 'escreva' '(' expressao expressoes ')'
+    // cmdEscreva : 'escreva' '(' escrita escritas ')'
     CMDEscreva,
 
-    // cmdSe : 'se' expressao 'entao' cmds senao 'fim_se'
+    // escrita : expressao formato
+    Escrita,
+
+    // escritas : ',' escrita escritas | <<vazio>>
+    Escritas,
+
+    // formato : ':' exp_aritmetica precisao | <<vazio>>
+    // precisao : ':' exp_aritmetica | <<vazio>>
+    Formato,
+
+    // cmdSe : 'se' expressao 'entao' cmds senao_se 'fim_se'
     CMDSe,
 
+    // senao_se : 'senao' 'se' expressao 'entao' cmds senao_se | senao
+    SenaoSe,
+
     // senao : 'senao' cmds | <<vazio>>
     Senao,
 
@@ -129,6 +160,10 @@ pub enum RegraAST {
     // cmdRetorne : 'retorne' expressao
     CMDRetorne,
 
+    // cmdAfirme : 'afirme' expressao
+    // guarda o texto da expressao como escrito no programa
+    CMDAfirme(String),
+
     // selecao : item_selecao selecao | <<vazio>>
     Selecao,
 
@@ -139,12 +174,16 @@ pub enum RegraAST {
     Constantes,
 
     // numero_intervalo : op_unario NUM_INT numero_intervalo2
+    //     | IDENT ident_intervalo2
+    //     | CARACTERE caractere_intervalo2
     NumeroIntervalo,
     
     // numero_intervalos : ',' numero_intervalo numero_intervalos | <<vazio>>
     NumeroIntervalos,
 
     // numero_intervalo2 : '..' op_unario NUM_INT | <<vazio>>
+    // ident_intervalo2 : '..' IDENT | <<vazio>>
+    // caractere_intervalo2 : '..' CARACTERE | <<vazio>>
     NumeroIntervalo2,
 
     // op_unario : '-' | <<vazio>>
@@ -168,16 +207,20 @@ pub enum RegraAST {
     // fatores : op2 fator fatores | <<vazio>>
     Fatores,
     
-    // op2 : '*' | '/'
+    // op2 : '*' | '/' | 'div'
     Op2 (Token),
     
-    // parcela : op_unario parcela_unario | parcela_nao_unario
+    // parcela : op_unario fator_potencia | parcela_nao_unario
     Parcela,
+
+    // fator_potencia : parcela_unario potencia
+    // potencia : '**' op_unario fator_potencia | <<vazio>>
+    Potencia,
     
     // parcelas : op3 parcela parcelas | <<vazio>>
     Parcelas,
 
-    // op3 : '%'
+    // op3 : '%' | 'mod'
     Op3,
     
     // parcela_unario : circunflexo identificador
@@ -201,6 +244,9 @@ pub enum RegraAST {
     //     | CADEIA
     Cadeia (Token),
 
+    //     | CARACTERE
+    Caractere (Token),
+
     // exp_relacional : exp_aritmetica exp_relacional2
     ExpRelacional,
 
@@ -246,7 +292,7 @@ pub enum RegraAST {
     FechaEscopo,
 
     Vazio,
-    Erro { mensagem: String },
+    Erro { diagnostico: Box<Diagnostico> },
 }
 
 /// estrutura generica para um no da arvore sintatica\
@@ -255,6 +301,8 @@ pub enum RegraAST {
 pub struct NoAST {
     regra: RegraAST,
     filhos: Vec<NoAST>,
+    // trecho da palavra-chave ou simbolo que inicia o no, que nao eh guardado como filho
+    inicio: Option<Trecho>,
 }
 
 
@@ -265,14 +313,27 @@ impl NoAST {
         Self {
             regra,
             filhos,
+            inicio: None,
         }
     }
 
+    /// retorna filhos do no, consumindo-o
+    pub fn into_filhos(self) -> Vec<NoAST> {
+        self.filhos
+    }
+
+    /// retorna o no iniciado pela palavra-chave ou simbolo de trecho `inicio`
+    pub fn com_inicio(mut self, inicio: Trecho) -> Self {
+        self.inicio = Some(inicio);
+        self
+    }
+
     /// retorna no folha
     pub fn new_folha(regra: RegraAST) -> Self {
         Self {
             regra,
             filhos: vec![],
+            inicio: None,
         }
     }
 
@@ -282,6 +343,7 @@ impl NoAST {
         Self {
             regra: RegraAST::Vazio,
             filhos: vec![],
+            inicio: None,
         }
     }
 
@@ -297,17 +359,17 @@ impl NoAST {
 
     /// verifica se `self` representa um erro sintatico
     pub fn is_erro(&self) -> bool {
-        if let RegraAST::Erro { mensagem: _ } = self.regra {
+        if let RegraAST::Erro { diagnostico: _ } = self.regra {
             true
         } else {
             false
         }
     }
 
-    /// retorna mensagem de erro caso `self` represente um erro sintatico
-    pub fn get_erro(&self) -> Option<String> {
-        if let RegraAST::Erro { mensagem } = &self.regra {
-            Some(mensagem.to_string())
+    /// retorna erro caso `self` represente um erro sintatico
+    pub fn get_erro(&self) -> Option<Diagnostico> {
+        if let RegraAST::Erro { diagnostico } = &self.regra {
+            Some(*diagnostico.clone())
         } else {
             None
         }
@@ -331,6 +393,15 @@ impl NoAST {
 
     /// retorna linha do no recursivamente
     pub fn linha(&self) -> u32 {
+        self.trecho().linha
+    }
+
+    /// retorna trecho do primeiro token do no que possui linha, recursivamente,
+    /// comecando pela palavra-chave ou simbolo que inicia o no, caso exista
+    pub fn trecho(&self) -> Trecho {
+        if let Some(inicio) = self.inicio {
+            return inicio
+        }
         match &self.regra {
             RegraAST::ValorConstante (token)
             | RegraAST::Ident (token)
@@ -340,16 +411,13 @@ impl NoAST {
             | RegraAST::NumInt (token)
             | RegraAST::NumReal (token)
             | RegraAST::Cadeia (token)
-            | RegraAST::OpRelacional (token) => token.linha(),
-            _ => {
-                if self.filhos.len() > 0 {
-                    for filho in &self.filhos {
-                        if filho.linha() > 0 { return filho.linha()}
-                    }
-                    0
-                }
-                else {0}
-            }
+            | RegraAST::Caractere (token)
+            | RegraAST::ConstanteLogica (token)
+            | RegraAST::OpRelacional (token) => token.trecho(),
+            _ => self.filhos.iter()
+                .map(|filho| filho.trecho())
+                .find(|trecho| trecho.linha > 0)
+                .unwrap_or_default()
         }
     }
     
@@ -358,8 +426,10 @@ impl NoAST {
         match &self.regra {
             RegraAST::Ident (token) => vec![token.clone()],
             RegraAST::TipoExtendido
-            | RegraAST::Registro
-            | RegraAST::Dimensao => vec![],
+            | RegraAST::Registro(_)
+            | RegraAST::Enumeracao(_)
+            | RegraAST::Dimensao
+            | RegraAST::Inicializacao => vec![],
             _ => {
                 let mut idents = vec![];
                 for filho in &self.filhos {
@@ -370,74 +440,208 @@ impl NoAST {
         }
     }
 
-    /// retorna atributos de um registro ou parametros de uma funcao ou procedimento recursivamente
-    pub fn variaveis(&self, escopos: &Escopos) -> Vec<NoAST> {
+    /// retorna argumentos de uma chamada ou valores de uma inicializacao recursivamente, em ordem inversa
+    pub fn variaveis(&self) -> Vec<NoAST> {
         match &self.regra {
-            RegraAST::Variavel => vec![self.clone()],
-            RegraAST::Variaveis => {
-                let mut vars = self.filhos[1].variaveis(escopos);
-                vars.push(self.filhos[0].clone());
-                vars
-            },
-            RegraAST::Registro => self.filhos[0].variaveis(escopos),
-            
-            RegraAST::Parametro => vec![self.clone()],
-            RegraAST::Parametros => {
-                let mut params = self.filhos[1].variaveis(escopos);
-                params.push(self.filhos[0].clone());
-                params
-            },
-            RegraAST::Parametros2 => {
-                let mut params = self.filhos[1].variaveis(escopos);
-                params.push(self.filhos[0].clone());
-                params
-            },
-            RegraAST::DeclaracaoFuncao
-            | RegraAST::DeclaracaoProcedimento => self.filhos[1].variaveis(escopos),
-            
-            RegraAST::ParcelaUnario2 => {
-                let mut exps = self.filhos[2].variaveis(escopos);
+            RegraAST::ParcelaUnario2
+            | RegraAST::CMDChamada => {
+                let mut exps = self.filhos[2].variaveis();
                 exps.push(self.filhos[1].clone());
                 exps
             },
-            RegraAST::Expressoes => {
-                let mut exps = self.filhos[1].variaveis(escopos);
+            RegraAST::Expressoes
+            | RegraAST::ValoresIniciais => {
+                let mut exps = self.filhos[1].variaveis();
                 exps.push(self.filhos[0].clone());
                 exps
             }
 
-            RegraAST::Ident(ident) => {
-                let nome = ident.lexema();
-                if escopos.existe(&nome) {
-                    let tipo = escopos.verificar(&nome).unwrap().tipo();
-                    match tipo {
-                        TipoSimbolo::Funcao { parametros, retorno: _ } => parametros,
-                        TipoSimbolo::Procedimento(parametros) => parametros,
-                        TipoSimbolo::Registro(atributos) => atributos,
-                        _ => vec![]
-                    }
-                } else {
-                    vec![]
+            _ => vec![]
+        }
+    }
+
+    /// retorna nos variavel que declaram os campos de um registro, em ordem
+    pub fn atributos(&self) -> Vec<&NoAST> {
+        match &self.regra {
+            RegraAST::Registro(_) => self.filhos[0].atributos(),
+            RegraAST::Variaveis => {
+                let mut atributos = vec![&self.filhos[0]];
+                atributos.append(&mut self.filhos[1].atributos());
+                atributos
+            }
+            _ => vec![]
+        }
+    }
+
+    /// retorna nos identificador declarados por uma variavel ou parametro, em ordem
+    pub fn identificadores(&self) -> Vec<&NoAST> {
+        match &self.regra {
+            RegraAST::Parametro => {
+                let mut identificadores = vec![&self.filhos[1]];
+                identificadores.append(&mut self.filhos[2].identificadores());
+                identificadores
+            }
+            RegraAST::Variavel => {
+                let mut identificadores = vec![&self.filhos[0]];
+                identificadores.append(&mut self.filhos[1].identificadores());
+                identificadores
+            }
+            RegraAST::Identificadores => {
+                let mut identificadores = vec![&self.filhos[0]];
+                identificadores.append(&mut self.filhos[1].identificadores());
+                identificadores
+            }
+            _ => vec![]
+        }
+    }
+
+    /// retorna nos parametro de uma declaracao de procedimento ou funcao, em ordem
+    pub fn parametros(&self) -> Vec<&NoAST> {
+        match &self.regra {
+            RegraAST::DeclaracaoProcedimento
+            | RegraAST::DeclaracaoFuncao => self.filhos[1].parametros(),
+            RegraAST::Parametros
+            | RegraAST::Parametros2 => {
+                let mut parametros = vec![&self.filhos[0]];
+                parametros.append(&mut self.filhos[1].parametros());
+                parametros
+            }
+            _ => vec![]
+        }
+    }
+
+    /// retorna expressoes que definem o tamanho de cada dimensao de um identificador, em ordem
+    pub fn dimensoes(&self) -> Vec<&NoAST> {
+        match &self.regra {
+            RegraAST::Identificador => self.filhos[2].dimensoes(),
+            RegraAST::Dimensao => {
+                let mut dimensoes = vec![&self.filhos[0]];
+                dimensoes.append(&mut self.filhos[1].dimensoes());
+                dimensoes
+            }
+            _ => vec![]
+        }
+    }
+
+    /// retorna tamanhos das dimensoes declaradas por um identificador, `None` nas que nao sao constantes
+    pub fn tamanhos(&self, escopos: &Escopos) -> Vec<Option<i32>> {
+        self.dimensoes().iter().map(|dimensao| dimensao.valor_inteiro(escopos)).collect()
+    }
+
+    /// retorna valor de uma expressao inteira formada apenas por um numero ou uma constante, possivelmente negados
+    pub fn valor_inteiro(&self, escopos: &Escopos) -> Option<i32> {
+        match &self.regra {
+            RegraAST::NumInt(token) => token.lexema().parse().ok(),
+            RegraAST::Expressao
+            | RegraAST::TermoLogico
+            | RegraAST::ExpRelacional
+            | RegraAST::ExpAritmetica
+            | RegraAST::Termo
+            | RegraAST::Fator => {
+                if self.filhos[1].regra == RegraAST::Vazio {
+                    self.filhos[0].valor_inteiro(escopos)
+                } else { None }
+            }
+            RegraAST::FatorLogico => {
+                if self.filhos[0].regra == RegraAST::Vazio {
+                    self.filhos[1].valor_inteiro(escopos)
+                } else { None }
+            }
+            RegraAST::Parcela => {
+                let valor = self.filhos[1].valor_inteiro(escopos)?;
+                if self.filhos[0].regra == RegraAST::OpUnario { Some(-valor) } else { Some(valor) }
+            }
+            RegraAST::ParcelaUnario3 => self.filhos[0].valor_inteiro(escopos),
+            RegraAST::ParcelaUnario1
+            | RegraAST::Identificador => {
+                let identificador = self.identificador()?;
+                if identificador.filhos[1].regra != RegraAST::Vazio || identificador.filhos[2].regra != RegraAST::Vazio {
+                    return None
                 }
+                escopos.verificar(&identificador.filhos[0].texto())?.valor()
+            }
+            _ => None
+        }
+    }
+
+    /// retorna o no identificador que forma sozinho uma expressao, caso ela seja apenas uma variavel
+    pub fn identificador(&self) -> Option<&NoAST> {
+        match &self.regra {
+            RegraAST::Identificador => Some(self),
+            RegraAST::Expressao
+            | RegraAST::TermoLogico
+            | RegraAST::ExpRelacional
+            | RegraAST::ExpAritmetica
+            | RegraAST::Termo
+            | RegraAST::Fator => {
+                if self.filhos[1].regra == RegraAST::Vazio {
+                    self.filhos[0].identificador()
+                } else { None }
+            }
+            RegraAST::FatorLogico
+            | RegraAST::Parcela
+            | RegraAST::ParcelaUnario1 => {
+                if self.filhos[0].regra == RegraAST::Vazio {
+                    self.filhos[1].identificador()
+                } else { None }
+            }
+            _ => None
+        }
+    }
+
+    /// retorna valor de uma expressao logica formada apenas por 'verdadeiro' ou 'falso', possivelmente negada
+    pub fn constante_logica(&self) -> Option<bool> {
+        match &self.regra {
+            RegraAST::ConstanteLogica(token) => Some(token.lexema() == "verdadeiro"),
+            RegraAST::Expressao
+            | RegraAST::TermoLogico => {
+                if self.filhos[1].regra == RegraAST::Vazio {
+                    self.filhos[0].constante_logica()
+                } else { None }
+            }
+            RegraAST::FatorLogico => {
+                let valor = self.filhos[1].constante_logica()?;
+                if self.filhos[0].regra == RegraAST::Nao { Some(!valor) } else { Some(valor) }
+            }
+            _ => None
+        }
+    }
+
+    /// retorna nomes dos tipos registro declarados por uma sequencia de declaracoes
+    pub fn registros(&self) -> Vec<String> {
+        match &self.regra {
+            RegraAST::Declaracoes
+            | RegraAST::DeclaracoesLocais => {
+                let mut registros = self.filhos[0].registros();
+                registros.append(&mut self.filhos[1].registros());
+                registros
             }
+            RegraAST::DeclaracaoTipo(_) if matches!(self.filhos[1].regra, RegraAST::Registro(_)) => vec![self.filhos[0].texto()],
+            _ => vec![]
+        }
+    }
 
+    /// retorna nomes das constantes de uma enumeracao, na ordem em que foram declaradas
+    pub fn valores(&self) -> Vec<String> {
+        match &self.regra {
+            RegraAST::Enumeracao(_)
+            | RegraAST::ValoresEnumeracao => {
+                let mut valores = vec![self.filhos[0].texto()];
+                valores.append(&mut self.filhos[1].valores());
+                valores
+            }
             _ => vec![]
         }
     }
 
-    pub fn intervalo(&self) -> (i32, i32) {
+    /// retorna extremos de um intervalo de selecao\
+    /// constantes enumeradas sao convertidas em sua posicao na enumeracao e caracteres em seu codigo
+    pub fn intervalo(&self, escopos: &Escopos) -> (i32, i32) {
         match &self.regra {
             RegraAST::NumeroIntervalo => {
-                let mut num1: i32 = self.filhos[1].texto().parse().unwrap();
-                if *self.filhos[0].regra() == RegraAST::OpUnario {
-                    num1 = -num1;
-                }
+                let num1 = Self::valor_intervalo(&self.filhos[0], &self.filhos[1], escopos);
                 let num2 = if self.filhos[2].regra != RegraAST::Vazio {
-                    let mut aux: i32 = self.filhos[2].filhos[1].texto().parse().unwrap();
-                    if *self.filhos[2].filhos[0].regra() == RegraAST::OpUnario {
-                        aux = -aux;
-                    }
-                    aux
+                    Self::valor_intervalo(&self.filhos[2].filhos[0], &self.filhos[2].filhos[1], escopos)
                 } else { num1 };
                 (num1, num2)
             }
@@ -445,17 +649,39 @@ impl NoAST {
         }
     }
 
+    /// retorna valor de um extremo de intervalo, formado por `op_unario` e um NUM_INT, IDENT ou CARACTERE `valor`
+    fn valor_intervalo(op_unario: &NoAST, valor: &NoAST, escopos: &Escopos) -> i32 {
+        let num = if let RegraAST::Ident(ident) = valor.regra() {
+            match escopos.tipos().resolver(valor.tipo(escopos)) {
+                TipoSimbolo::Enumeracao(id) => {
+                    escopos.tipos().enumeracao(id).valores.iter().position(|nome| *nome == ident.lexema()).unwrap_or(0) as i32
+                }
+                _ => 0
+            }
+        } else if let RegraAST::Caractere(caractere) = valor.regra() {
+            Self::codigo_caractere(&caractere.lexema())
+        } else {
+            valor.texto().parse().unwrap()
+        };
+
+        if *op_unario.regra() == RegraAST::OpUnario { -num } else { num }
+    }
+
     /// retorna tipo do no recursivamente
     pub fn tipo(&self, escopos: &Escopos) -> TipoSimbolo {
         match &self.regra {
             RegraAST::ConstanteLogica(_token) => TipoSimbolo::Logico,
             RegraAST::Cadeia(_token) => TipoSimbolo::Cadeia,
+            RegraAST::Caractere(_token) => TipoSimbolo::Caractere,
             RegraAST::NumInt(_token) => TipoSimbolo::Inteiro,
             RegraAST::NumReal(_token) => TipoSimbolo::Real,
-            RegraAST::Registro => TipoSimbolo::Registro(self.variaveis(escopos)),
-            RegraAST::DeclaracaoProcedimento => TipoSimbolo::Procedimento(self.variaveis(escopos)),
+            // tipos declarados sao resolvidos ao serem registrados na tabela de tipos
+            RegraAST::DeclaracaoTipo(declaracao)
+            | RegraAST::Registro(declaracao)
+            | RegraAST::Enumeracao(declaracao) => escopos.tipos().declarado(*declaracao).unwrap_or(TipoSimbolo::Invalido),
+            RegraAST::DeclaracaoProcedimento => TipoSimbolo::Procedimento(self.parametros_resolvidos(escopos)),
             RegraAST::DeclaracaoFuncao => TipoSimbolo::Funcao {
-                parametros: self.variaveis(escopos),
+                parametros: self.parametros_resolvidos(escopos),
                 retorno: Box::new(self.filhos[2].tipo(escopos)),
             },
             RegraAST::TipoBasico (token) => {
@@ -464,6 +690,8 @@ impl NoAST {
                     TipoToken::PCinteiro => TipoSimbolo::Inteiro,
                     TipoToken::PCreal => TipoSimbolo::Real,
                     TipoToken::PClogico => TipoSimbolo::Logico,
+                    TipoToken::PCarquivo => TipoSimbolo::Arquivo,
+                    TipoToken::PCcaractere => TipoSimbolo::Caractere,
                     _ => TipoSimbolo::Vazio
                 }
             },
@@ -472,13 +700,28 @@ impl NoAST {
                 TipoSimbolo::Ponteiro(Box::new(self.filhos[0].tipo(escopos)))
             }
             
-            RegraAST::ParcelaUnario2
-            | RegraAST::ParcelaUnario3 => self.filhos[0].tipo(escopos),
+            // a conversao para um apelido de enumeracao, como em Tom(2), resulta em um valor da enumeracao base,
+            // e chamadas com argumentos incompativeis nao produzem valor
+            RegraAST::ParcelaUnario2 => match escopos.tipos().resolver(self.filhos[0].tipo(escopos)) {
+                TipoSimbolo::Funcao { parametros, retorno: _ } if !self.argumentos_compativeis(&parametros, escopos) => TipoSimbolo::Invalido,
+                // funcoes embutidas com uma versao para cada tipo de argumento resultam no tipo da versao chamada
+                tipo @ TipoSimbolo::Funcao { parametros: _, retorno: _ } => self.embutida(escopos).map_or(tipo, |embutida| embutida.tipo()),
+                tipo => tipo
+            },
+
+            RegraAST::ParcelaUnario3 => self.filhos[0].tipo(escopos),
 
             RegraAST::FatorLogico
-            | RegraAST::ParcelaUnario1
             | RegraAST::ExpRelacional2 => self.filhos[1].tipo(escopos),
 
+            // '^' desreferencia o ponteiro, resultando no tipo apontado
+            RegraAST::ParcelaUnario1 => {
+                match (&self.filhos[0].regra, self.filhos[1].tipo(escopos)) {
+                    (RegraAST::Circunflexo, TipoSimbolo::Ponteiro(tipo)) => escopos.tipos().resolver(*tipo),
+                    (_, tipo) => tipo,
+                }
+            }
+
             RegraAST::Variavel => self.filhos[2].tipo(escopos),
 
             RegraAST::Parametro => self.filhos[3].tipo(escopos),
@@ -492,22 +735,41 @@ impl NoAST {
                 }
             }
 
+            RegraAST::Termo
+            | RegraAST::Fator => {
+                // operacoes sao avaliadas da esquerda para a direita
+                let mut tipo = self.filhos[0].tipo_valor(escopos);
+                let mut operacoes = &self.filhos[1];
+                while operacoes.regra != RegraAST::Vazio {
+                    let operando = operacoes.filhos[1].tipo_valor(escopos);
+                    tipo = Self::tipo_operacao(&operacoes.filhos[0], tipo, operando);
+                    operacoes = &operacoes.filhos[2];
+                }
+                tipo
+            }
+
+            // potencias de numeros sao sempre reais, como o resultado de pow em C
+            RegraAST::Potencia => {
+                let base = self.filhos[0].tipo_valor(escopos);
+                let expoente = self.filhos[2].tipo_valor(escopos);
+                let numerico = |tipo: &TipoSimbolo| *tipo == TipoSimbolo::Real || *tipo == TipoSimbolo::Inteiro;
+                if numerico(&base) && numerico(&expoente) {
+                    TipoSimbolo::Real
+                } else {
+                    TipoSimbolo::Invalido
+                }
+            }
+
             RegraAST::Expressao
             | RegraAST::TermoLogico
-            | RegraAST::ExpAritmetica
-            | RegraAST::Termo
-            | RegraAST::Fator => {
+            | RegraAST::ExpAritmetica => {
                 if self.filhos.is_empty() {
                     return TipoSimbolo::Vazio;
                 }
 
-                let tipo1 = if let TipoSimbolo::Funcao { parametros: _, retorno } = self.filhos[0].tipo(escopos) {
-                    *retorno
-                } else { self.filhos[0].tipo(escopos) };
+                let tipo1 = self.filhos[0].tipo_valor(escopos);
 
-                let tipo2 = if let TipoSimbolo::Funcao { parametros: _, retorno} = self.filhos[1].tipo(escopos) {
-                    *retorno
-                } else { self.filhos[1].tipo(escopos) };
+                let tipo2 = self.filhos[1].tipo_valor(escopos);
 
                 if tipo1 == tipo2 || ((tipo1 == TipoSimbolo::Real || tipo1 == TipoSimbolo::Inteiro) && (tipo2 == TipoSimbolo::Real || tipo2 == TipoSimbolo::Inteiro)) || tipo2 == TipoSimbolo::Vazio {
                     tipo1
@@ -526,13 +788,9 @@ impl NoAST {
                     return TipoSimbolo::Vazio;
                 }
 
-                let tipo1 = if let TipoSimbolo::Funcao { parametros: _, retorno } = self.filhos[1].tipo(escopos) {
-                    *retorno
-                } else { self.filhos[1].tipo(escopos) };
+                let tipo1 = self.filhos[1].tipo_valor(escopos);
 
-                let tipo2 = if let TipoSimbolo::Funcao { parametros: _, retorno } = self.filhos[2].tipo(escopos) {
-                    *retorno
-                } else { self.filhos[2].tipo(escopos) };
+                let tipo2 = self.filhos[2].tipo_valor(escopos);
                 
                 if tipo1 == tipo2 || ((tipo1 == TipoSimbolo::Real || tipo1 == TipoSimbolo::Inteiro) && (tipo2 == TipoSimbolo::Real || tipo2 == TipoSimbolo::Inteiro)) || tipo2 == TipoSimbolo::Vazio {
                     tipo1
@@ -546,17 +804,15 @@ impl NoAST {
                     return TipoSimbolo::Vazio;
                 }
 
-                let tipo1 = if let TipoSimbolo::Funcao { parametros: _, retorno } = self.filhos[0].tipo(escopos) {
-                    *retorno
-                } else { self.filhos[0].tipo(escopos) };
+                let tipo1 = self.filhos[0].tipo_valor(escopos);
 
-                let tipo2 = if let TipoSimbolo::Funcao { parametros: _, retorno } = self.filhos[1].tipo(escopos) {
-                    *retorno
-                } else { self.filhos[1].tipo(escopos) };
-                
+                let tipo2 = self.filhos[1].tipo_valor(escopos);
+
+                // comparacoes entre operandos validos de tipos incompativeis sao relatadas pelo analisador semantico,
+                // e continuam produzindo um valor logico
                 if tipo2 == TipoSimbolo::Vazio {
                     tipo1
-                } else if tipo1 == tipo2 || ((tipo1 == TipoSimbolo::Real || tipo1 == TipoSimbolo::Inteiro) && (tipo2 == TipoSimbolo::Real || tipo2 == TipoSimbolo::Inteiro)) || tipo2 == TipoSimbolo::Vazio {
+                } else if self.comparaveis(escopos) || (tipo1 != TipoSimbolo::Invalido && tipo2 != TipoSimbolo::Invalido) {
                     TipoSimbolo::Logico
                 } else {
                     TipoSimbolo::Invalido
@@ -572,11 +828,11 @@ impl NoAST {
             }
 
             RegraAST::Identificador => {
-                let nome = format!("{}{}", self.filhos[0].texto(), self.filhos[1].texto());
-                if escopos.existe(&nome) {
-                    escopos.verificar(&nome).unwrap().tipo()
-                } else {
-                    TipoSimbolo::Invalido
+                match self.declaracao(escopos).map(|(tipo, tamanhos)| (escopos.tipos().resolver(tipo), tamanhos)) {
+                    // um indice alem das dimensoes declaradas de um literal seleciona um de seus caracteres
+                    Some((TipoSimbolo::Cadeia, tamanhos)) if self.dimensoes().len() > tamanhos.len() => TipoSimbolo::Caractere,
+                    Some((tipo, _)) => tipo,
+                    None => TipoSimbolo::Invalido
                 }
             }
 
@@ -593,6 +849,175 @@ impl NoAST {
         }
     }
 
+    /// retorna tipo e tamanhos das dimensoes declaradas da variavel ou campo acessado por um identificador,
+    /// `None` caso nao tenha sido declarado\
+    /// campos sao buscados na tabela de tipos, no registro acessado ou no registro apontado no caso de `p^.campo`
+    pub fn declaracao(&self, escopos: &Escopos) -> Option<(TipoSimbolo, Vec<Option<i32>>)> {
+        let simbolo = escopos.verificar(&self.filhos[0].texto())?;
+        let mut declaracao = (simbolo.tipo(), simbolo.tamanhos());
+        let mut acesso = &self.filhos[1];
+        while acesso.regra != RegraAST::Vazio {
+            let id = match (&acesso.regra, escopos.tipos().resolver(declaracao.0)) {
+                (RegraAST::AcessoPonteiro, TipoSimbolo::Ponteiro(tipo)) => match *tipo {
+                    TipoSimbolo::Registro(id) => id,
+                    _ => return None
+                },
+                (RegraAST::Identificador2, TipoSimbolo::Registro(id)) => id,
+                _ => return None
+            };
+
+            let nome = acesso.filhos[0].texto();
+            let campo = escopos.tipos().registro(id).campos.iter().find(|campo| campo.nome == nome)?;
+            declaracao = (campo.tipo.clone(), campo.tamanhos.clone());
+            acesso = &acesso.filhos[1];
+        }
+        Some(declaracao)
+    }
+
+    /// retorna parametros de uma declaracao de procedimento ou funcao, com seus tipos resolvidos, em ordem
+    fn parametros_resolvidos(&self, escopos: &Escopos) -> Vec<Parametro> {
+        let mut parametros = vec![];
+        for parametro in self.parametros() {
+            let tipo = parametro.tipo(escopos);
+            let referencia = parametro.filhos[0].regra == RegraAST::Var;
+            for identificador in parametro.identificadores() {
+                parametros.push(Parametro {
+                    nome: identificador.filhos[0].texto(),
+                    tipo: tipo.clone(),
+                    tamanhos: identificador.tamanhos(escopos),
+                    referencia,
+                });
+            }
+        }
+        parametros
+    }
+
+    /// verifica se os operandos de uma exp_relacional podem ser comparados\
+    /// numeros sao comparaveis entre si, e registros so podem ser comparados quanto a igualdade, campo a campo
+    pub fn comparaveis(&self, escopos: &Escopos) -> bool {
+        let tipo1 = self.filhos[0].tipo_valor(escopos);
+        let tipo2 = self.filhos[1].tipo_valor(escopos);
+        let igualdade = self.filhos[1].filhos.first().is_some_and(|op| matches!(op.texto().trim(), "=" | "<>"));
+        let numerico = |tipo: &TipoSimbolo| *tipo == TipoSimbolo::Real || *tipo == TipoSimbolo::Inteiro;
+
+        if matches!(tipo1, TipoSimbolo::Registro(..)) && !igualdade {
+            false
+        } else {
+            tipo1 == tipo2 || (numerico(&tipo1) && numerico(&tipo2))
+        }
+    }
+
+    /// verifica quantidade e tipos dos argumentos de uma chamada a funcao ou procedimento com parametros `parametros`,
+    /// sem considerar o modo de passagem
+    pub fn argumentos_compativeis(&self, parametros: &[Parametro], escopos: &Escopos) -> bool {
+        // argumentos sao retornados em ordem inversa
+        let mut argumentos = self.variaveis();
+        argumentos.reverse();
+
+        match self.embutida(escopos) {
+            Some(embutida) => embutida.aceita(&argumentos.iter().map(|argumento| argumento.tipo_valor(escopos)).collect::<Vec<_>>()),
+            None => argumentos.len() == parametros.len()
+                && argumentos.iter().zip(parametros).all(|(argumento, parametro)| escopos.tipos().equivalentes(&parametro.tipo, &argumento.tipo_nominal(escopos))),
+        }
+    }
+
+    /// retorna funcao embutida chamada pelo no, na versao que aceita os tipos dos argumentos, caso o nome chamado seja embutido
+    pub fn embutida(&self, escopos: &Escopos) -> Option<&'static Embutida> {
+        let nome = self.filhos[0].texto();
+        if !escopos.embutido(&nome) {
+            return None
+        }
+        // argumentos sao retornados em ordem inversa
+        let tipos: Vec<TipoSimbolo> = self.variaveis().iter().rev().map(|argumento| argumento.tipo_valor(escopos)).collect();
+        biblioteca::escolher(&nome, &tipos)
+    }
+
+    /// verifica se a expressao contem chamada com argumentos incompativeis ou chamada de procedimento,
+    /// cujo erro ja eh relatado pela propria chamada
+    pub fn contem_chamada_invalida(&self, escopos: &Escopos) -> bool {
+        match self.regra {
+            RegraAST::ParcelaUnario2 if matches!(self.tipo(escopos), TipoSimbolo::Procedimento(_)) => true,
+            RegraAST::ParcelaUnario2 if self.tipo(escopos) == TipoSimbolo::Invalido
+                && matches!(self.filhos[0].tipo(escopos), TipoSimbolo::Funcao { parametros: _, retorno: _ }) => true,
+            _ => self.filhos.iter().any(|filho| filho.contem_chamada_invalida(escopos)),
+        }
+    }
+
+    /// verifica se a expressao usa como valor um tipo, procedimento ou funcao sem argumentos,
+    /// cujo erro ja eh relatado pelo proprio identificador
+    pub fn contem_nao_valor(&self, escopos: &Escopos) -> bool {
+        match self.regra {
+            RegraAST::Identificador => {
+                let categoria = escopos.verificar(&self.filhos[0].texto()).map(|simbolo| simbolo.categoria());
+                matches!(categoria, Some(Categoria::Tipo | Categoria::Procedimento | Categoria::Funcao))
+            }
+            _ => self.filhos.iter().any(|filho| filho.contem_nao_valor(escopos)),
+        }
+    }
+
+    /// retorna tipo do valor produzido pelo no, que no caso de uma chamada de funcao eh seu tipo de retorno
+    pub fn tipo_valor(&self, escopos: &Escopos) -> TipoSimbolo {
+        match self.tipo(escopos) {
+            TipoSimbolo::Funcao { parametros: _, retorno } => escopos.tipos().resolver(*retorno),
+            tipo => tipo,
+        }
+    }
+
+    /// retorna tipo do valor produzido pelo no, preservando o apelido com que foi declarado caso o valor seja
+    /// uma variavel, campo, ponteiro desreferenciado ou resultado de funcao\
+    /// demais expressoes produzem valores do tipo base, como em C
+    pub fn tipo_nominal(&self, escopos: &Escopos) -> TipoSimbolo {
+        match &self.regra {
+            RegraAST::Identificador => match self.declaracao(escopos) {
+                Some((tipo, tamanhos)) if self.dimensoes().len() <= tamanhos.len() => tipo,
+                _ => self.tipo(escopos)
+            },
+            RegraAST::Expressao
+            | RegraAST::TermoLogico
+            | RegraAST::ExpRelacional
+            | RegraAST::ExpAritmetica
+            | RegraAST::Termo
+            | RegraAST::Fator if self.filhos[1].regra == RegraAST::Vazio => self.filhos[0].tipo_nominal(escopos),
+            RegraAST::FatorLogico
+            | RegraAST::Parcela if self.filhos[0].regra == RegraAST::Vazio => self.filhos[1].tipo_nominal(escopos),
+            RegraAST::ParcelaUnario1 => match (&self.filhos[0].regra, self.filhos[1].tipo_nominal(escopos)) {
+                (RegraAST::Circunflexo, TipoSimbolo::Ponteiro(tipo)) => *tipo,
+                (RegraAST::Vazio, tipo) => tipo,
+                _ => self.tipo(escopos)
+            },
+            RegraAST::ParcelaUnario2 => match self.tipo(escopos) {
+                TipoSimbolo::Funcao { parametros: _, retorno } => *retorno,
+                tipo => tipo
+            },
+            RegraAST::ParcelaUnario3 => self.filhos[0].tipo_nominal(escopos),
+            RegraAST::ParcelaNaoUnario => TipoSimbolo::Ponteiro(Box::new(self.filhos[0].tipo_nominal(escopos))),
+            _ => self.tipo_valor(escopos)
+        }
+    }
+
+    /// retorna tipo do resultado da operacao `operador` entre valores dos tipos `tipo1` e `tipo2`\
+    /// `div`, `mod` e `%` exigem operandos inteiros
+    fn tipo_operacao(operador: &NoAST, tipo1: TipoSimbolo, tipo2: TipoSimbolo) -> TipoSimbolo {
+        let inteira = match &operador.regra {
+            RegraAST::Op2(token) => token.tipo() == TipoToken::PCdiv,
+            RegraAST::Op3 => true,
+            _ => false
+        };
+        let numerico = |tipo: &TipoSimbolo| *tipo == TipoSimbolo::Real || *tipo == TipoSimbolo::Inteiro;
+
+        if inteira {
+            if tipo1 == TipoSimbolo::Inteiro && tipo2 == TipoSimbolo::Inteiro {
+                TipoSimbolo::Inteiro
+            } else {
+                TipoSimbolo::Invalido
+            }
+        } else if tipo1 == tipo2 || (numerico(&tipo1) && numerico(&tipo2)) {
+            tipo1
+        } else {
+            TipoSimbolo::Invalido
+        }
+    }
+
     /// retorna texto do no recursivamente
     pub fn texto(&self) -> String {
         match &self.regra {
@@ -601,14 +1026,24 @@ impl NoAST {
             | RegraAST::TipoBasico (token)
             | RegraAST::NumInt (token)
             | RegraAST::NumReal (token)
-            | RegraAST::Cadeia (token) => token.lexema(),
+            | RegraAST::Cadeia (token)
+            | RegraAST::Caractere (token) => token.lexema(),
             RegraAST::Op1 (token)
             | RegraAST::Op2 (token)
             | RegraAST::OpRelacional (token) => format!(" {} ", token.lexema()),
             RegraAST::Op3 => " % ".to_string(),
+            RegraAST::ConstanteLogica (token) => token.lexema(),
+            RegraAST::OpLogico1 => " ou ".to_string(),
+            RegraAST::OpLogico2 => " e ".to_string(),
+            RegraAST::Nao => "nao ".to_string(),
+            RegraAST::ParcelaUnario2 => format!("{}({}{})", self.filhos[0].texto(), self.filhos[1].texto(), self.filhos[2].texto()),
+            RegraAST::ParcelaUnario3 => format!("({})", self.filhos[0].texto()),
+            RegraAST::Potencia => format!("{} ** {}{}", self.filhos[0].texto(), self.filhos[1].texto(), self.filhos[2].texto()),
+            RegraAST::ParcelaNaoUnario => format!("&{}", self.filhos[0].texto()),
             RegraAST::OpUnario => "-".to_string(),
             RegraAST::Circunflexo => "^".to_string(),
             RegraAST::Identificador2 => format!(".{}{}", self.filhos[0].texto(), self.filhos[1].texto()),
+            RegraAST::AcessoPonteiro => format!("^.{}{}", self.filhos[0].texto(), self.filhos[1].texto()),
             RegraAST::Identificadores
             | RegraAST::Expressoes => format!(", {}{}", self.filhos[0].texto(), self.filhos[1].texto()),
             RegraAST::Dimensao => format!("[{}]{}", self.filhos[0].texto(), self.filhos[1].texto()),
@@ -621,4 +1056,17 @@ impl NoAST {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// retorna codigo de um literal CARACTERE, interpretando as sequencias de escape do C
+    fn codigo_caractere(lexema: &str) -> i32 {
+        let conteudo: Vec<char> = lexema.chars().skip(1).take(lexema.chars().count() - 2).collect();
+        match conteudo.as_slice() {
+            ['\\', 'n'] => '\n' as i32,
+            ['\\', 't'] => '\t' as i32,
+            ['\\', '0'] => 0,
+            ['\\', c] => *c as i32,
+            [c, ..] => *c as i32,
+            [] => 0,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    diagnostico::{Codigo, Diagnostico},
     lexico::{
         Lexico,
         token::{Token, TipoToken}
@@ -14,6 +15,12 @@ pub struct Parser {
     buffer_tokens: Vec<Token>,
     lex: Lexico,
     end: bool,
+    // quantidade de declaracoes de tipo, registros e enumeracoes ja analisados, usada para numera-los
+    declaracoes: usize,
+    // tokens lidos desde o inicio da expressao do afirme sendo analisado
+    lidos: Option<Vec<Token>>,
+    // comandos lidos apos o ultimo se de uma cadeia 'senao se', que pertencem ao bloco que contem a cadeia
+    resto_cadeia: Option<NoAST>,
 }
 
 impl Parser {
@@ -25,11 +32,20 @@ impl Parser {
             buffer_tokens,
             lex,
             end: false,
+            declaracoes: 0,
+            lidos: None,
+            resto_cadeia: None,
         };
         parser.read_token();
         parser
     }
 
+    /// retorna numero da proxima declaracao de tipo, registro ou enumeracao
+    fn nova_declaracao(&mut self) -> usize {
+        self.declaracoes += 1;
+        self.declaracoes
+    }
+
     /// recarrega buffer, le proximo token e retorna token consumido
     fn read_token(&mut self) -> Token {
 
@@ -50,6 +66,9 @@ impl Parser {
         }
 
         // println!("Lido: {}", &self.lookahead(1).tipo_string());
+        if let Some(lidos) = &mut self.lidos {
+            lidos.push(anterior.clone());
+        }
         anterior
     }
 
@@ -76,24 +95,25 @@ impl Parser {
         self.buffer_tokens[k - 1].copy()
     }
 
-    /// retorna mensagem de erro constando linha e lexema atuais\
+    /// retorna erro constando linha e lexema atuais\
     /// todo erro lexico ou sintatico eh propagado ate o no raiz recursivamente
     fn erro_sintatico(&mut self) -> NoAST {
-        let linha = self.lookahead(1).linha();
+        let trecho = self.lookahead(1).trecho();
         let lexema = self.lookahead(1).lexema();
-        let mensagem = if self.lookahead(1).tipo() == TipoToken::Erro {
-            lexema
+        let diagnostico = if self.lookahead(1).tipo() == TipoToken::Erro {
+            // a analise para no primeiro erro, entao um token de erro corresponde sempre ao primeiro erro lexico
+            self.lex.diagnosticos()[0].clone()
         } else {
-            format!("Linha {}: erro sintatico proximo a {}\n", linha, lexema)
+            Diagnostico::erro(Codigo::ErroSintatico, trecho, format!("erro sintatico proximo a {}", lexema))
         };
         
-        NoAST::new(RegraAST::Erro { mensagem }, vec![]) 
+        NoAST::new(RegraAST::Erro { diagnostico: Box::new(diagnostico) }, vec![]) 
     }
 
     // as funcoes a seguir representam as leis de formacao da gramatica da linguagem LA
 
     /// inicia a analise sintatica\
-    /// programa : declaracoes 'algoritmo' corpo 'fim_algoritmo'
+    /// programa : declaracoes 'algoritmo' corpo 'fim_algoritmo' fecha_escopo
     pub fn programa(&mut self) -> NoAST {
         let mut filhos = vec![];
 
@@ -124,6 +144,8 @@ impl Parser {
             Err(erro_sintatico) => return erro_sintatico
         }
         
+        filhos.push(NoAST::new_folha(RegraAST::FechaEscopo));
+
         NoAST::new(RegraAST::Programa, filhos)
     }
 
@@ -211,7 +233,7 @@ impl Parser {
                 }
                 filhos.push(tipo);
 
-                NoAST::new(RegraAST::DeclaracaoTipo, filhos)
+                NoAST::new(RegraAST::DeclaracaoTipo(self.nova_declaracao()), filhos)
             }
             TipoToken::PCconstante => {
                 match self.match_(TipoToken::PCconstante) {
@@ -253,7 +275,7 @@ impl Parser {
         }
     }
 
-    /// variavel : identificador identificadores ':' tipo
+    /// variavel : identificador identificadores ':' tipo inicializacao
     fn variavel(&mut self) -> NoAST {
         let mut filhos = vec![];
 
@@ -280,9 +302,72 @@ impl Parser {
         }
         filhos.push(tipo);
 
+        let inicializacao = self.inicializacao();
+        if inicializacao.is_erro() {
+            return inicializacao
+        }
+        filhos.push(inicializacao);
+
         NoAST::new(RegraAST::Variavel, filhos)
     }
 
+    /// inicializacao : '<-' valor_inicial | <<vazio>>
+    fn inicializacao(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        match self.lookahead(1).tipo() {
+            TipoToken::BackArrow => {
+                match self.match_(TipoToken::BackArrow) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                let valor_inicial = self.valor_inicial();
+                if valor_inicial.is_erro() {
+                    return valor_inicial
+                }
+                filhos.push(valor_inicial);
+
+                NoAST::new(RegraAST::Inicializacao, filhos)
+            }
+            _ => NoAST::vazio()
+        }
+    }
+
+    /// valor_inicial : expressao | '[' expressao expressoes ']'
+    fn valor_inicial(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        match self.lookahead(1).tipo() {
+            TipoToken::AbreCol => {
+                match self.match_(TipoToken::AbreCol) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                let expressao = self.expressao();
+                if expressao.is_erro() {
+                    return expressao
+                }
+                filhos.push(expressao);
+
+                let expressoes = self.expressoes();
+                if expressoes.is_erro() {
+                    return expressoes
+                }
+                filhos.push(expressoes);
+
+                match self.match_(TipoToken::FechaCol) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                NoAST::new(RegraAST::ValoresIniciais, filhos)
+            }
+            _ => self.expressao()
+        }
+    }
+
     /// identificador : IDENT identificador2 dimensao
     fn identificador(&mut self) -> NoAST {
         let mut filhos = vec![];
@@ -308,7 +393,9 @@ impl Parser {
         NoAST::new(RegraAST::Identificador, filhos)
     }
 
-    /// identificador2 : '.' IDENT identificador2 | <<vazio>>
+    /// identificador2 : '.' IDENT identificador2
+    ///     | '^' '.' IDENT identificador2
+    ///     | <<vazio>>
     fn identificador2(&mut self) -> NoAST {
         let mut filhos = vec![];
 
@@ -333,6 +420,32 @@ impl Parser {
 
                 NoAST::new(RegraAST::Identificador2, filhos)
             }
+            // um '^' sem '.' a seguir inicia o proximo comando, como em `^p <- x`
+            TipoToken::Circunflexo if self.lookahead(2).tipo() == TipoToken::Ponto => {
+                match self.match_(TipoToken::Circunflexo) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                match self.match_(TipoToken::Ponto) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+                
+                let ident = match self.match_(TipoToken::Ident) {
+                    Ok(token) => NoAST::new_folha(RegraAST::Ident(token)),
+                    Err(erro_sintatico) => return erro_sintatico
+                };
+                filhos.push(ident);
+                
+                let identificador2 = self.identificador2();
+                if identificador2.is_erro() {
+                    return identificador2
+                }
+                filhos.push(identificador2);
+
+                NoAST::new(RegraAST::AcessoPonteiro, filhos)
+            }
             _ => NoAST::vazio()
         }
     }
@@ -400,21 +513,24 @@ impl Parser {
         }
     }
 
-    /// tipo : registro | tipo_estendido
+    /// tipo : registro | enumeracao | tipo_estendido
     fn tipo(&mut self) -> NoAST {
         match self.lookahead(1).tipo() {
             TipoToken::PCregistro => self.registro(),
+            TipoToken::AbrePar => self.enumeracao(),
             TipoToken::Circunflexo
             | TipoToken::PCliteral
             | TipoToken::PCinteiro
             | TipoToken::PCreal
             | TipoToken::PClogico
+            | TipoToken::PCarquivo
+            | TipoToken::PCcaractere
             | TipoToken::Ident => self.tipo_estendido(),
             _ => self.erro_sintatico()
         }
     }
 
-    /// tipo_basico : 'literal' | 'inteiro' | 'real' | 'logico'
+    /// tipo_basico : 'literal' | 'inteiro' | 'real' | 'logico' | 'arquivo' | 'caractere'
     fn tipo_basico(&mut self) -> NoAST {
         let token = match self.lookahead(1).tipo() {
             TipoToken::PCliteral => match self.match_(TipoToken::PCliteral) {
@@ -433,6 +549,14 @@ impl Parser {
                 Ok(token) => token,
                 Err(erro_sintatico) => return erro_sintatico
             }
+            TipoToken::PCarquivo => match self.match_(TipoToken::PCarquivo) {
+                Ok(token) => token,
+                Err(erro_sintatico) => return erro_sintatico
+            }
+            TipoToken::PCcaractere => match self.match_(TipoToken::PCcaractere) {
+                Ok(token) => token,
+                Err(erro_sintatico) => return erro_sintatico
+            }
             _ => return self.erro_sintatico()
         };
         
@@ -445,7 +569,9 @@ impl Parser {
             TipoToken::PCliteral
             | TipoToken::PCinteiro
             | TipoToken::PCreal
-            | TipoToken::PClogico => self.tipo_basico(),
+            | TipoToken::PClogico
+            | TipoToken::PCarquivo
+            | TipoToken::PCcaractere => self.tipo_basico(),
             TipoToken::Ident => {
                 let ident = match self.match_(TipoToken::Ident) {
                     Ok(token) => token,
@@ -480,20 +606,24 @@ impl Parser {
     fn circunflexo(&mut self) -> NoAST {
         match self.lookahead(1).tipo() {
             TipoToken::Circunflexo => match self.match_(TipoToken::Circunflexo) {
-                Ok(_) => NoAST::new_folha(RegraAST::Circunflexo),
+                Ok(token) => NoAST::new_folha(RegraAST::Circunflexo).com_inicio(token.trecho()),
                 Err(erro_sintatico) => return erro_sintatico
             }
             _ => NoAST::vazio()
         }
     }
 
-    /// valor_constante : CADEIA | NUM_INT | NUM_REAL | 'verdadeiro' | 'falso'
+    /// valor_constante : CADEIA | CARACTERE | NUM_INT | NUM_REAL | 'verdadeiro' | 'falso'
     fn valor_constante(&mut self) -> NoAST {
         let token = match self.lookahead(1).tipo() {
             TipoToken::Cadeia => match self.match_(TipoToken::Cadeia) {
                 Ok(token) => token,
                 Err(erro_sintatico) => return erro_sintatico
             }
+            TipoToken::Caractere => match self.match_(TipoToken::Caractere) {
+                Ok(token) => token,
+                Err(erro_sintatico) => return erro_sintatico
+            }
             TipoToken::NumInt => match self.match_(TipoToken::NumInt) {
                 Ok(token) => token,
                 Err(erro_sintatico) => return erro_sintatico
@@ -538,9 +668,67 @@ impl Parser {
         
         filhos.push(NoAST::new_folha(RegraAST::FechaEscopo));
         
-        NoAST::new(RegraAST::Registro, filhos)
+        NoAST::new(RegraAST::Registro(self.nova_declaracao()), filhos)
     }
     
+    /// enumeracao : '(' IDENT valores_enumeracao ')'
+    fn enumeracao(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        match self.match_(TipoToken::AbrePar) {
+            Ok(_) => {},
+            Err(erro_sintatico) => return erro_sintatico
+        }
+
+        let ident = match self.match_(TipoToken::Ident) {
+            Ok(token) => NoAST::new_folha(RegraAST::Ident(token)),
+            Err(erro_sintatico) => return erro_sintatico
+        };
+        filhos.push(ident);
+
+        let valores_enumeracao = self.valores_enumeracao();
+        if valores_enumeracao.is_erro() {
+            return valores_enumeracao
+        }
+        filhos.push(valores_enumeracao);
+
+        match self.match_(TipoToken::FechaPar) {
+            Ok(_) => {},
+            Err(erro_sintatico) => return erro_sintatico
+        }
+
+        NoAST::new(RegraAST::Enumeracao(self.nova_declaracao()), filhos)
+    }
+
+    /// valores_enumeracao : ',' IDENT valores_enumeracao | <<vazio>>
+    fn valores_enumeracao(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        match self.lookahead(1).tipo() {
+            TipoToken::Virgula => {
+                match self.match_(TipoToken::Virgula) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                let ident = match self.match_(TipoToken::Ident) {
+                    Ok(token) => NoAST::new_folha(RegraAST::Ident(token)),
+                    Err(erro_sintatico) => return erro_sintatico
+                };
+                filhos.push(ident);
+
+                let valores_enumeracao = self.valores_enumeracao();
+                if valores_enumeracao.is_erro() {
+                    return valores_enumeracao
+                }
+                filhos.push(valores_enumeracao);
+
+                NoAST::new(RegraAST::ValoresEnumeracao, filhos)
+            }
+            _ => NoAST::vazio()
+        }
+    }
+
     /// variaveis : variavel variaveis | <<vazio>>
     fn variaveis(&mut self) -> NoAST {
         let mut filhos = vec![];
@@ -831,7 +1019,7 @@ impl Parser {
     }
 
     /// cmd : cmdLeia | cmdEscreva | cmdSe | cmdCaso | cmdPara| cmdEnquanto
-    ///     | cmdFaca | cmdAtribuicao | cmdChamada | cmdRetorne
+    ///     | cmdFaca | cmdAtribuicao | cmdChamada | cmdRetorne | cmdAfirme
     fn cmd(&mut self) -> NoAST {
         match self.lookahead(1).tipo() {
             TipoToken::PCleia => self.cmd_leia(),
@@ -849,25 +1037,50 @@ impl Parser {
                 }
             }
             TipoToken::PCretorne => self.cmd_retorne(),
+            TipoToken::PCafirme => self.cmd_afirme(),
             _ => self.erro_sintatico()
         }
     }
 
     /// cmds : cmd cmds | <<vazio>>
     fn cmds(&mut self) -> NoAST {
+        self.sequencia_cmds(false)
+    }
+
+    /// cmds de um item_selecao, que terminam antes do proximo item_selecao
+    fn cmds_selecao(&mut self) -> NoAST {
+        self.sequencia_cmds(true)
+    }
+
+    /// cmds : cmd cmds | <<vazio>>\
+    /// em um item_selecao, IDENT seguido de ':', ',' ou '..' inicia o proximo item com constantes enumeradas, e nao um comando
+    fn sequencia_cmds(&mut self, selecao: bool) -> NoAST {
         let mut filhos = vec![];
 
+        if selecao && self.lookahead(1).tipo() == TipoToken::Ident {
+            match self.lookahead(2).tipo() {
+                TipoToken::Delim | TipoToken::Virgula | TipoToken::PontoPonto => return NoAST::vazio(),
+                _ => {}
+            }
+        }
+
         match self.lookahead(1).tipo() {
             TipoToken::PCleia | TipoToken::PCescreva | TipoToken::PCse | TipoToken::PCcaso
             | TipoToken::PCpara | TipoToken::PCenquanto | TipoToken::PCfaca | TipoToken::Circunflexo
-            | TipoToken::Ident | TipoToken::PCretorne => {
+            | TipoToken::Ident | TipoToken::PCretorne | TipoToken::PCafirme => {
                 let cmd = self.cmd();
                 if cmd.is_erro() {
                     return cmd
                 }
                 filhos.push(cmd);
 
-                let cmds = self.cmds();
+                // os comandos que seguem uma cadeia 'senao se' ja foram lidos, e terminam junto com o bloco
+                if let Some(resto) = self.resto_cadeia.take() {
+                    filhos.push(resto);
+                    return NoAST::new(RegraAST::CMDs, filhos)
+                }
+
+                let cmds = self.sequencia_cmds(selecao);
                 if cmds.is_erro() {
                     return cmds
                 }
@@ -881,6 +1094,7 @@ impl Parser {
 
     /// cmdLeia : 'leia' '(' circunflexo identificador cmdLeia2 ')'
     fn cmd_leia(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.match_(TipoToken::PCleia) {
@@ -916,7 +1130,7 @@ impl Parser {
             Err(erro_sintatico) => return erro_sintatico
         }
         
-        NoAST::new(RegraAST::CMDLeia, filhos)
+        NoAST::new(RegraAST::CMDLeia, filhos).com_inicio(inicio)
     }
 
     /// cmdLeia2 : ',' circunflexo identificador cmdLeia2 | <<vazio>>
@@ -954,8 +1168,9 @@ impl Parser {
         }
     }
 
-    /// cmdEscreva : 'escreva' '(' expressao expressoes ')'
+    /// cmdEscreva : 'escreva' '(' escrita escritas ')'
     fn cmd_escreva(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.match_(TipoToken::PCescreva) {
@@ -968,28 +1183,121 @@ impl Parser {
             Err(erro_sintatico) => return erro_sintatico
         }
         
-        let expressao = self.expressao();
-        if expressao.is_erro() {
-            return expressao
+        let escrita = self.escrita();
+        if escrita.is_erro() {
+            return escrita
         }
-        filhos.push(expressao);
+        filhos.push(escrita);
 
-        let expressoes = self.expressoes();
-        if expressoes.is_erro() {
-            return expressoes
+        let escritas = self.escritas();
+        if escritas.is_erro() {
+            return escritas
         }
-        filhos.push(expressoes);
+        filhos.push(escritas);
 
         match self.match_(TipoToken::FechaPar) {
             Ok(_) => {},
             Err(erro_sintatico) => return erro_sintatico
         }
         
-        NoAST::new(RegraAST::CMDEscreva, filhos)
+        NoAST::new(RegraAST::CMDEscreva, filhos).com_inicio(inicio)
+    }
+
+    /// escrita : expressao formato
+    fn escrita(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        let expressao = self.expressao();
+        if expressao.is_erro() {
+            return expressao
+        }
+        filhos.push(expressao);
+
+        let formato = self.formato();
+        if formato.is_erro() {
+            return formato
+        }
+        filhos.push(formato);
+
+        NoAST::new(RegraAST::Escrita, filhos)
     }
 
-    /// cmdSe : 'se' expressao 'entao' cmds senao 'fim_se'
+    /// escritas : ',' escrita escritas | <<vazio>>
+    fn escritas(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        match self.lookahead(1).tipo() {
+            TipoToken::Virgula => {
+                match self.match_(TipoToken::Virgula) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                let escrita = self.escrita();
+                if escrita.is_erro() {
+                    return escrita
+                }
+                filhos.push(escrita);
+
+                let escritas = self.escritas();
+                if escritas.is_erro() {
+                    return escritas
+                }
+                filhos.push(escritas);
+
+                NoAST::new(RegraAST::Escritas, filhos)
+            }
+            _ => NoAST::vazio()
+        }
+    }
+
+    /// formato : ':' exp_aritmetica precisao | <<vazio>>
+    fn formato(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        match self.lookahead(1).tipo() {
+            TipoToken::Delim => {
+                match self.match_(TipoToken::Delim) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                let largura = self.exp_aritmetica();
+                if largura.is_erro() {
+                    return largura
+                }
+                filhos.push(largura);
+
+                let precisao = self.precisao();
+                if precisao.is_erro() {
+                    return precisao
+                }
+                filhos.push(precisao);
+
+                NoAST::new(RegraAST::Formato, filhos)
+            }
+            _ => NoAST::vazio()
+        }
+    }
+
+    /// precisao : ':' exp_aritmetica | <<vazio>>
+    fn precisao(&mut self) -> NoAST {
+        match self.lookahead(1).tipo() {
+            TipoToken::Delim => {
+                match self.match_(TipoToken::Delim) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                self.exp_aritmetica()
+            }
+            _ => NoAST::vazio()
+        }
+    }
+
+    /// cmdSe : 'se' expressao 'entao' cmds senao_se 'fim_se'
     fn cmd_se(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.match_(TipoToken::PCse) {
@@ -1014,18 +1322,54 @@ impl Parser {
         }
         filhos.push(cmds);
 
-        let senao = self.senao();
+        let senao = self.senao_se();
         if senao.is_erro() {
             return senao
         }
+        let encadeado = *senao.regra() == RegraAST::SenaoSe;
         filhos.push(senao);
 
-        match self.match_(TipoToken::PCfimSe) {
+        // o 'fim_se' do ultimo se de uma cadeia encerra tambem os anteriores
+        if !encadeado {
+            match self.match_(TipoToken::PCfimSe) {
+                Ok(_) => {},
+                Err(erro_sintatico) => return erro_sintatico
+            }
+        }
+        
+        NoAST::new(RegraAST::CMDSe, filhos).com_inicio(inicio)
+    }
+
+    /// senao_se : 'senao' 'se' expressao 'entao' cmds senao_se | senao\
+    /// 'senao' seguido de 'se' eh ambiguo: caso os comandos do senao sejam seguidos de 'fim_se', o se eh um comando aninhado,
+    /// e o 'fim_se' encerra o se externo; do contrario, o se continua a cadeia, e seu 'fim_se' encerra toda ela
+    fn senao_se(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
+
+        if self.lookahead(1).tipo() != TipoToken::PCsenao || self.lookahead(2).tipo() != TipoToken::PCse {
+            return self.senao()
+        }
+
+        match self.match_(TipoToken::PCsenao) {
             Ok(_) => {},
             Err(erro_sintatico) => return erro_sintatico
         }
-        
-        NoAST::new(RegraAST::CMDSe, filhos)
+
+        // IDENT seguido de ':', ',' ou '..' nunca inicia um comando, e pode ser o proximo item de um caso que contem a cadeia
+        let cmds = self.cmds_selecao();
+        if cmds.is_erro() {
+            return cmds
+        }
+
+        if self.lookahead(1).tipo() == TipoToken::PCfimSe {
+            return NoAST::new(RegraAST::Senao, vec![cmds])
+        }
+
+        // comandos lidos apos o se pertencem ao bloco que contem a cadeia
+        let mut filhos = cmds.into_filhos();
+        self.resto_cadeia = filhos.pop();
+        let se = filhos.pop().unwrap();
+        NoAST::new(RegraAST::SenaoSe, se.into_filhos()).com_inicio(inicio)
     }
 
     /// senao : 'senao' cmds | <<vazio>>
@@ -1053,6 +1397,7 @@ impl Parser {
 
     /// cmdCaso : 'caso' exp_aritmetica 'seja' selecao senao 'fim_caso'
     fn cmd_caso(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.match_(TipoToken::PCcaso) {
@@ -1088,11 +1433,12 @@ impl Parser {
             Err(erro_sintatico) => return erro_sintatico
         }
         
-        NoAST::new(RegraAST::CMDCaso, filhos)
+        NoAST::new(RegraAST::CMDCaso, filhos).com_inicio(inicio)
     }
 
     /// cmdPara : 'para' IDENT '<-' exp_aritmetica 'ate' exp_aritmetica 'faca' cmds 'fim_para'
     fn cmd_para(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.match_(TipoToken::PCpara) {
@@ -1144,11 +1490,12 @@ impl Parser {
             Err(erro_sintatico) => return erro_sintatico
         }
         
-        NoAST::new(RegraAST::CMDPara, filhos)
+        NoAST::new(RegraAST::CMDPara, filhos).com_inicio(inicio)
     }
 
     /// cmdEnquanto : 'enquanto' expressao 'faca' cmds 'fim_enquanto'
     fn cmd_enquanto(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.match_(TipoToken::PCenquanto) {
@@ -1178,11 +1525,12 @@ impl Parser {
             Err(erro_sintatico) => return erro_sintatico
         }
         
-        NoAST::new(RegraAST::CMDEnquanto, filhos)
+        NoAST::new(RegraAST::CMDEnquanto, filhos).com_inicio(inicio)
     }
 
     /// cmdFaca : 'faca' cmds 'ate' expressao
     fn cmd_faca(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.match_(TipoToken::PCfaca) {
@@ -1207,7 +1555,7 @@ impl Parser {
         }
         filhos.push(expressao);
 
-        NoAST::new(RegraAST::CMDFaca, filhos)
+        NoAST::new(RegraAST::CMDFaca, filhos).com_inicio(inicio)
     }
 
     /// cmdAtribuicao : circunflexo identificador '<-' expressao
@@ -1277,6 +1625,7 @@ impl Parser {
 
     /// cmdRetorne : 'retorne' expressao
     fn cmd_retorne(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.match_(TipoToken::PCretorne) {
@@ -1290,7 +1639,28 @@ impl Parser {
         }
         filhos.push(expressao);
 
-        NoAST::new(RegraAST::CMDRetorne, filhos)
+        NoAST::new(RegraAST::CMDRetorne, filhos).com_inicio(inicio)
+    }
+
+    /// cmdAfirme : 'afirme' expressao
+    fn cmd_afirme(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
+        let mut filhos = vec![];
+
+        match self.match_(TipoToken::PCafirme) {
+            Ok(_) => {},
+            Err(erro_sintatico) => return erro_sintatico
+        }
+        
+        self.lidos = Some(vec![]);
+        let expressao = self.expressao();
+        let lidos = self.lidos.take().unwrap_or_default();
+        if expressao.is_erro() {
+            return expressao
+        }
+        filhos.push(expressao);
+
+        NoAST::new(RegraAST::CMDAfirme(texto_original(&lidos)), filhos).com_inicio(inicio)
     }
 
     /// selecao : item_selecao selecao | <<vazio>>
@@ -1298,7 +1668,7 @@ impl Parser {
         let mut filhos = vec![];
 
         match self.lookahead(1).tipo() {
-            TipoToken::OpAritSub | TipoToken::NumInt => {
+            TipoToken::OpAritSub | TipoToken::NumInt | TipoToken::Ident | TipoToken::Caractere => {
                 let item_selecao = self.item_selecao();
                 if item_selecao.is_erro() {
                     return item_selecao
@@ -1332,7 +1702,7 @@ impl Parser {
             Err(erro_sintatico) => return erro_sintatico
         }
         
-        let cmds = self.cmds();
+        let cmds = self.cmds_selecao();
         if cmds.is_erro() {
             return cmds
         }
@@ -1361,9 +1731,45 @@ impl Parser {
     }
     
     /// numero_intervalo : op_unario NUM_INT numero_intervalo2
+    ///     | IDENT ident_intervalo2
+    ///     | CARACTERE caractere_intervalo2
     fn numero_intervalo(&mut self) -> NoAST {
         let mut filhos = vec![];
 
+        if self.lookahead(1).tipo() == TipoToken::Caractere {
+            let caractere = match self.match_(TipoToken::Caractere) {
+                Ok(token) => NoAST::new_folha(RegraAST::Caractere(token)),
+                Err(erro_sintatico) => return erro_sintatico
+            };
+            filhos.push(NoAST::vazio());
+            filhos.push(caractere);
+
+            let caractere_intervalo2 = self.caractere_intervalo2();
+            if caractere_intervalo2.is_erro() {
+                return caractere_intervalo2
+            }
+            filhos.push(caractere_intervalo2);
+
+            return NoAST::new(RegraAST::NumeroIntervalo, filhos)
+        }
+
+        if self.lookahead(1).tipo() == TipoToken::Ident {
+            let ident = match self.match_(TipoToken::Ident) {
+                Ok(token) => NoAST::new_folha(RegraAST::Ident(token)),
+                Err(erro_sintatico) => return erro_sintatico
+            };
+            filhos.push(NoAST::vazio());
+            filhos.push(ident);
+
+            let ident_intervalo2 = self.ident_intervalo2();
+            if ident_intervalo2.is_erro() {
+                return ident_intervalo2
+            }
+            filhos.push(ident_intervalo2);
+
+            return NoAST::new(RegraAST::NumeroIntervalo, filhos)
+        }
+
         let op_unario = self.op_unario();
         if op_unario.is_erro() {
             return op_unario
@@ -1442,17 +1848,68 @@ impl Parser {
             _ => NoAST::vazio()
         }
     }
-    
+
+    /// ident_intervalo2 : '..' IDENT | <<vazio>>\
+    /// representado por um no numero_intervalo2 sem operador unario
+    fn ident_intervalo2(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        match self.lookahead(1).tipo() {
+            TipoToken::PontoPonto => {
+                match self.match_(TipoToken::PontoPonto) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                let ident = match self.match_(TipoToken::Ident) {
+                    Ok(token) => NoAST::new_folha(RegraAST::Ident(token)),
+                    Err(erro_sintatico) => return erro_sintatico
+                };
+                filhos.push(NoAST::vazio());
+                filhos.push(ident);
+
+                NoAST::new(RegraAST::NumeroIntervalo2, filhos)
+            }
+            _ => NoAST::vazio()
+        }
+    }
+
+    /// caractere_intervalo2 : '..' CARACTERE | <<vazio>>\
+    /// representado por um no numero_intervalo2 sem operador unario
+    fn caractere_intervalo2(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        match self.lookahead(1).tipo() {
+            TipoToken::PontoPonto => {
+                match self.match_(TipoToken::PontoPonto) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                let caractere = match self.match_(TipoToken::Caractere) {
+                    Ok(token) => NoAST::new_folha(RegraAST::Caractere(token)),
+                    Err(erro_sintatico) => return erro_sintatico
+                };
+                filhos.push(NoAST::vazio());
+                filhos.push(caractere);
+
+                NoAST::new(RegraAST::NumeroIntervalo2, filhos)
+            }
+            _ => NoAST::vazio()
+        }
+    }
+
     /// op_unario : '-' | <<vazio>>
     fn op_unario(&mut self) -> NoAST {
         match self.lookahead(1).tipo() {
             TipoToken::OpAritSub => {
+                let inicio = self.lookahead(1).trecho();
                 match self.match_(TipoToken::OpAritSub) {
                     Ok(_) => {},
                     Err(erro_sintatico) => return erro_sintatico
                 }
                 
-                NoAST::new_folha(RegraAST::OpUnario)
+                NoAST::new_folha(RegraAST::OpUnario).com_inicio(inicio)
             }
             _ => NoAST::vazio()
         }
@@ -1550,7 +2007,7 @@ impl Parser {
         let mut filhos = vec![];
 
         match self.lookahead(1).tipo() {
-            TipoToken::OpAritMult | TipoToken::OpAritDiv => {
+            TipoToken::OpAritMult | TipoToken::OpAritDiv | TipoToken::PCdiv => {
                 let op2 = self.op2();
                 if op2.is_erro() {
                     return op2
@@ -1591,7 +2048,7 @@ impl Parser {
         NoAST::new_folha(RegraAST::Op1(token))
     }
     
-    /// op2 : '*' | '/'
+    /// op2 : '*' | '/' | 'div'
     fn op2(&mut self) -> NoAST {
         let token = match self.lookahead(1).tipo() {
             TipoToken::OpAritMult => match self.match_(TipoToken::OpAritMult) {
@@ -1602,20 +2059,27 @@ impl Parser {
                 Ok(token) => token,
                 Err(erro_sintatico) => return erro_sintatico
             }
+            TipoToken::PCdiv => match self.match_(TipoToken::PCdiv) {
+                Ok(token) => token,
+                Err(erro_sintatico) => return erro_sintatico
+            }
             _ => return self.erro_sintatico()
         };
         NoAST::new_folha(RegraAST::Op2(token))
     }
     
-    /// op3 : '%'
+    /// op3 : '%' | 'mod'
     fn op3(&mut self) -> NoAST {
-        match self.match_(TipoToken::Porcento) {
+        let tipo = if self.lookahead(1).tipo() == TipoToken::PCmod {
+            TipoToken::PCmod
+        } else { TipoToken::Porcento };
+        match self.match_(tipo) {
             Ok(_) => NoAST::new_folha(RegraAST::Op3),
             Err(erro_sintatico) => erro_sintatico
         }
     }
     
-    /// parcela : op_unario parcela_unario | parcela_nao_unario
+    /// parcela : op_unario fator_potencia | parcela_nao_unario
     fn parcela(&mut self) -> NoAST {
         let mut filhos = vec![];
 
@@ -1632,26 +2096,65 @@ impl Parser {
                 }
                 filhos.push(op_unario);
 
-                let parcela_unario = self.parcela_unario();
-                if parcela_unario.is_erro() {
-                    return parcela_unario
+                let fator_potencia = self.fator_potencia();
+                if fator_potencia.is_erro() {
+                    return fator_potencia
                 }
-                filhos.push(parcela_unario);
+                filhos.push(fator_potencia);
 
                 NoAST::new(RegraAST::Parcela, filhos)
             }
             TipoToken::EComercial
-            | TipoToken::Cadeia => self.parcela_nao_unario(),
+            | TipoToken::Cadeia
+            | TipoToken::Caractere => self.parcela_nao_unario(),
             _ => self.erro_sintatico()
         }
     }
     
+    /// fator_potencia : parcela_unario potencia\
+    /// potencia : '**' op_unario fator_potencia | <<vazio>>\
+    /// sem '**' o proprio no parcela_unario eh retornado, e com '**' o expoente associa a direita
+    fn fator_potencia(&mut self) -> NoAST {
+        let mut filhos = vec![];
+
+        let parcela_unario = self.parcela_unario();
+        if parcela_unario.is_erro() {
+            return parcela_unario
+        }
+
+        match self.lookahead(1).tipo() {
+            TipoToken::OpAritPot => {
+                filhos.push(parcela_unario);
+
+                match self.match_(TipoToken::OpAritPot) {
+                    Ok(_) => {},
+                    Err(erro_sintatico) => return erro_sintatico
+                }
+
+                let op_unario = self.op_unario();
+                if op_unario.is_erro() {
+                    return op_unario
+                }
+                filhos.push(op_unario);
+
+                let fator_potencia = self.fator_potencia();
+                if fator_potencia.is_erro() {
+                    return fator_potencia
+                }
+                filhos.push(fator_potencia);
+
+                NoAST::new(RegraAST::Potencia, filhos)
+            }
+            _ => parcela_unario
+        }
+    }
+
     /// parcelas : op3 parcela parcelas | <<vazio>>
     fn parcelas(&mut self) -> NoAST {
         let mut filhos = vec![];
 
         match self.lookahead(1).tipo() {
-            TipoToken::Porcento => {
+            TipoToken::Porcento | TipoToken::PCmod => {
                 let op3 = self.op3();
                 if op3.is_erro() {
                     return op3
@@ -1737,6 +2240,7 @@ impl Parser {
                 }
             }
             TipoToken::AbrePar => {
+                let inicio = self.lookahead(1).trecho();
                 match self.match_(TipoToken::AbrePar) {
                     Ok(_) => {},
                     Err(erro_sintatico) => return erro_sintatico
@@ -1753,7 +2257,7 @@ impl Parser {
                     Err(erro_sintatico) => return erro_sintatico
                 }
                 
-                NoAST::new(RegraAST::ParcelaUnario3, filhos)
+                NoAST::new(RegraAST::ParcelaUnario3, filhos).com_inicio(inicio)
             }
             TipoToken::NumInt => match self.match_(TipoToken::NumInt) {
                 Ok(token) => NoAST::new_folha(RegraAST::NumInt(token)),
@@ -1769,6 +2273,7 @@ impl Parser {
     
     /// parcela_nao_unario : '&' identificador | CADEIA
     fn parcela_nao_unario(&mut self) -> NoAST {
+        let inicio = self.lookahead(1).trecho();
         let mut filhos = vec![];
 
         match self.lookahead(1).tipo() {
@@ -1784,12 +2289,16 @@ impl Parser {
                 }
                 filhos.push(identificador);
 
-                NoAST::new(RegraAST::ParcelaNaoUnario, filhos)
+                NoAST::new(RegraAST::ParcelaNaoUnario, filhos).com_inicio(inicio)
             }
             TipoToken::Cadeia => match self.match_(TipoToken::Cadeia) {
                 Ok(token) => NoAST::new_folha(RegraAST::Cadeia(token)),
                 Err(erro_sintatico) => return erro_sintatico
             }
+            TipoToken::Caractere => match self.match_(TipoToken::Caractere) {
+                Ok(token) => NoAST::new_folha(RegraAST::Caractere(token)),
+                Err(erro_sintatico) => return erro_sintatico
+            }
             _ => self.erro_sintatico()
         }
     }
@@ -2024,7 +2533,7 @@ impl Parser {
     fn nao(&mut self) -> NoAST {
         match self.lookahead(1).tipo() {
             TipoToken::PCnao => match self.match_(TipoToken::PCnao) {
-                Ok(_) => NoAST::new_folha(RegraAST::Nao),
+                Ok(token) => NoAST::new_folha(RegraAST::Nao).com_inicio(token.trecho()),
                 Err(erro_sintatico) => return erro_sintatico
             }
             _ => NoAST::vazio()
@@ -2042,7 +2551,8 @@ impl Parser {
             | TipoToken::NumReal
             | TipoToken::AbrePar
             | TipoToken::EComercial
-            | TipoToken::Cadeia  => self.exp_relacional(),
+            | TipoToken::Cadeia
+            | TipoToken::Caractere => self.exp_relacional(),
             _ => self.erro_sintatico()
         }
     }
@@ -2078,4 +2588,20 @@ impl Parser {
             Err(erro_sintatico) => return erro_sintatico
         }
     }
-}
\ No newline at end of file
+}
+
+/// retorna texto de `tokens` como escrito no programa, mantendo os espacos entre tokens de uma mesma linha
+fn texto_original(tokens: &[Token]) -> String {
+    let mut texto = String::new();
+    let mut fim = (0, 0);
+    for token in tokens {
+        let trecho = token.trecho();
+        if !texto.is_empty() {
+            let espacos = if trecho.linha == fim.0 { trecho.coluna.saturating_sub(fim.1) } else { 1 };
+            texto += &" ".repeat(espacos as usize);
+        }
+        texto += &token.lexema();
+        fim = (trecho.linha, trecho.coluna + trecho.comprimento);
+    }
+    texto
+}
@@ -0,0 +1,26 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int main(void) {
+	int x;
+	scanf("%d", &x);
+	if (x == 1) {
+		printf("%s", "um");
+	} else if (x == 2) {
+		printf("%s", "dois");
+	} else if (x == 3) {
+		printf("%s", "tres");
+	} else {
+		printf("%s", "outro");
+	}
+	if (x < 3) {
+		printf("%s", " pequeno");
+	} else if (x < 5) {
+		printf("%s", " medio");
+	} else {
+		printf("%s", " grande");
+	}
+	printf("%s", "!");
+	return 0;
+}
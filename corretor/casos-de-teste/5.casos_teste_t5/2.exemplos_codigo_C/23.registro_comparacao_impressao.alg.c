@@ -0,0 +1,62 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+typedef struct Item {
+	char nome[80];
+	float notas[2];
+} Item;
+typedef struct Turma {
+	char nomes[3][80];
+	Item item;
+	int codigo;
+} Turma;
+int chamadas;
+
+Turma criar(int codigo) {
+	Turma t;
+	chamadas = chamadas + 1;
+	t.codigo = codigo;
+	strcpy(t.nomes[0], "ana");
+	strcpy(t.nomes[1], "bia");
+	strcpy(t.nomes[2], "caio");
+	strcpy(t.item.nome, "x");
+	t.item.notas[0] = 1.0;
+	t.item.notas[1] = 2.0;
+	return t;
+}
+
+static int igual_Item(const Item *a, const Item *b) {
+	if (strcmp(a->nome, b->nome) != 0) return 0;
+	for (int i0 = 0; i0 < (int) (sizeof(a->notas) / sizeof(a->notas[0])); i0++) if (a->notas[i0] != b->notas[i0]) return 0;
+	return 1;
+}
+
+static int igual_Turma(const Turma *a, const Turma *b) {
+	for (int i0 = 0; i0 < (int) (sizeof(a->nomes) / sizeof(a->nomes[0])); i0++) if (strcmp(a->nomes[i0], b->nomes[i0]) != 0) return 0;
+	if (!igual_Item(&a->item, &b->item)) return 0;
+	if (a->codigo != b->codigo) return 0;
+	return 1;
+}
+
+int main(void) {
+	Turma a, b;
+	chamadas = 0;
+	a = criar(1);
+	b = criar(1);
+	strcpy(b.nomes[1], "bia");
+	if (igual_Turma(&a, &b)) {
+		printf("%s", "iguais ");
+	}
+	if (igual_Turma((Turma[]){criar(1)}, &a)) {
+		printf("%s", "iguais ");
+	}
+	b.item.notas[1] = 5.0;
+	if (!igual_Turma(&a, &b)) {
+		printf("%s", "diferentes ");
+	}
+	if (!igual_Turma((Turma[]){criar(1)}, (Turma[]){criar(2)})) {
+		printf("%s", "diferentes ");
+	}
+	printf("%d", chamadas);
+	return 0;
+}
@@ -0,0 +1,31 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+typedef float Metros;
+typedef char Nome;
+typedef enum { vermelho, verde, azul } Cor;
+typedef Cor Tom;
+
+Metros dobro(Metros d) {
+	return d * 2;
+}
+
+int main(void) {
+	Metros m;
+	Nome n[80];
+	Tom t;
+	m = dobro(1.5);
+	strcpy(n, "trena");
+	t = (Tom) (2);
+	switch (t) {
+		case vermelho:
+			printf("%s%s%f%s", n, " ", m, " vermelho");
+			break;
+		case azul:
+			printf("%s%s%f%s", n, " ", m, " azul");
+			break;
+		default:
+			printf("%s", "outro");
+	}
+	return 0;
+}
@@ -0,0 +1,73 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+typedef struct R {
+	int v;
+} R;
+typedef R C;
+struct la_registro2 {
+	char k;
+}  g, h;
+
+struct la_registro3 {
+	int c;
+	struct la_registro4 {
+		char n[2][80];
+	}  dentro;
+	struct la_registro3 *prox;
+};
+
+static int igual_la_registro4(const struct la_registro4 *a, const struct la_registro4 *b) {
+	for (int i0 = 0; i0 < (int) (sizeof(a->n) / sizeof(a->n[0])); i0++) if (strcmp(a->n[i0], b->n[i0]) != 0) return 0;
+	return 1;
+}
+
+static int igual_la_registro3(const struct la_registro3 *a, const struct la_registro3 *b) {
+	if (a->c != b->c) return 0;
+	if (!igual_la_registro4(&a->dentro, &b->dentro)) return 0;
+	if (a->prox != b->prox) return 0;
+	return 1;
+}
+
+static int igual_R(const R *a, const R *b) {
+	if (a->v != b->v) return 0;
+	return 1;
+}
+
+void p(int z) {
+	typedef enum { vermelho, azul } Cor;
+	typedef struct la_registro3 Q;
+	Q a, b;
+	C x, y;
+	a.c = azul;
+	b.c = azul;
+	strcpy(a.dentro.n[0], "um");
+	strcpy(b.dentro.n[0], "um");
+	strcpy(a.dentro.n[1], "d");
+	strcpy(b.dentro.n[1], "d");
+	a.prox = &a;
+	b.prox = &a;
+	x.v = z + 1;
+	y.v = 2;
+	if (igual_la_registro3(&a, &b)) {
+		printf("%s", "q ");
+	}
+	if (igual_R(&x, &y)) {
+		printf("%s", "c ");
+	}
+}
+
+static int igual_la_registro2(const struct la_registro2 *a, const struct la_registro2 *b) {
+	if (a->k != b->k) return 0;
+	return 1;
+}
+
+int main(void) {
+	g.k = 'a';
+	h.k = 'a';
+	if (igual_la_registro2(&g, &h)) {
+		printf("%s", "g ");
+	}
+	p(1);
+	return 0;
+}
@@ -0,0 +1,15 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <math.h>
+
+int main(void) {
+	int i;
+	float r;
+	scanf("%d", &i);
+	scanf("%f", &r);
+	
+	printf("%s%d%s%d%s%d", "inteiro: ", abs(i), " ", abs(i - 10) / 2, " ", abs(i) % 4);
+	printf("%s%f%s%f", "\nreal: ", fabs(r), " ", fabs(r) * 2);
+	return 0;
+}
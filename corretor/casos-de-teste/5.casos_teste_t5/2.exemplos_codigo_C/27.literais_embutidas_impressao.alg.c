@@ -0,0 +1,58 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <ctype.h>
+
+char *la_buffer(void) {
+	static char buffers[16][80];
+	static int atual = 0;
+	atual = (atual + 1) % 16;
+	return buffers[atual];
+}
+
+char *la_concatene(char *a, char *b) {
+	char *resultado = la_buffer();
+	snprintf(resultado, 80, "%s%s", a, b);
+	return resultado;
+}
+
+char *la_maiusculo(char *s) {
+	char *resultado = la_buffer();
+	int i;
+	for (i = 0; s[i] != '\0' && i < 79; i++) {
+		resultado[i] = toupper((unsigned char) s[i]);
+	}
+	resultado[i] = '\0';
+	return resultado;
+}
+
+char *la_subcadeia(char *s, int inicio, int tamanho) {
+	char *resultado = la_buffer();
+	int n = strlen(s);
+	if (inicio < 0) inicio = 0;
+	if (inicio > n) inicio = n;
+	if (tamanho < 0) tamanho = 0;
+	snprintf(resultado, 80, "%.*s", tamanho, s + inicio);
+	return resultado;
+}
+
+int main(void) {
+	char nome[80], sobrenome[80], completo[80];
+	scanf("%s", nome);
+	scanf("%s", sobrenome);
+	
+	strcpy(completo, la_concatene(la_concatene(nome, " "), sobrenome));
+	printf("%s%s%d%s", completo, " tem ", (int) strlen(completo), " letras\n");
+	printf("%s%s", la_maiusculo(completo), "\n");
+	printf("%s%s", la_subcadeia(completo, 0, 3), "\n");
+	if (strcmp(nome, "ana") == 0) {
+		printf("%s", "nome igual\n");
+	}
+	if (strcmp(nome, sobrenome) != 0) {
+		printf("%s", "nomes diferentes\n");
+	}
+	if (strcmp(la_concatene(nome, sobrenome), "anasilva") == 0) {
+		printf("%s", "concatenacao igual");
+	}
+	return 0;
+}
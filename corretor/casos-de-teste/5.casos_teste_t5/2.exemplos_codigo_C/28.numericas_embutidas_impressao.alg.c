@@ -0,0 +1,29 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <math.h>
+#include <time.h>
+
+int la_aleatorio(int n) {
+	static int semeado = 0;
+	if (!semeado) {
+		srand(time(NULL));
+		semeado = 1;
+	}
+	return n > 0 ? rand() % n : 0;
+}
+
+int main(void) {
+	float x;
+	int n, sorteio;
+	scanf("%f", &x);
+	scanf("%d", &n);
+	
+	printf("%0.2f%s%0.2f%s%0.1f%s", sqrt(x), " ", pow(x, 2), " ", pow(n, 3), "\n");
+	printf("%d%s%d%s%d%s%d%s", (int) round(x), " ", (int) trunc(x), " ", (int) round(-x), " ", (int) trunc(-x), "\n");
+	sorteio = la_aleatorio(n);
+	if (sorteio >= 0 && sorteio < n) {
+		printf("%s", "sorteio dentro do intervalo");
+	}
+	return 0;
+}
@@ -0,0 +1,39 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+typedef enum { domingo, segunda, terca, quarta, quinta, sexta, sabado } Dia;
+
+void classificar(Dia d) {
+	switch (d) {
+		case domingo:
+		case sabado:
+			printf("%s", "fim de semana");
+			break;
+		case segunda:
+		case terca:
+		case quarta:
+		case quinta:
+			printf("%s", "dia util");
+			break;
+		default:
+			printf("%s", "sexta");
+	}
+	printf("%s", "\n");
+}
+
+int main(void) {
+	Dia d;
+	int i;
+	scanf("%d", &i);
+	d = (Dia) (i);
+	printf("%d%s", (int) (d), " ");
+	classificar(d);
+	d = sexta;
+	printf("%d%s", (int) (d), " ");
+	classificar(d);
+	d = (Dia) ((int) (d) + 1);
+	if (d == sabado) {
+		classificar(d);
+	}
+	return 0;
+}
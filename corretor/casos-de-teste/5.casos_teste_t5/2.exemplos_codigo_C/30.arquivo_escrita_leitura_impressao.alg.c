@@ -0,0 +1,46 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <ctype.h>
+
+FILE *la_abra(char *nome, char *modo) {
+	FILE *arquivo = fopen(nome, strcmp(modo, "escrita") == 0 ? "w" : strcmp(modo, "acrescimo") == 0 ? "a" : "r");
+	if (arquivo == NULL) {
+		fprintf(stderr, "nao foi possivel abrir o arquivo %s\n", nome);
+		exit(1);
+	}
+	return arquivo;
+}
+
+int la_fim_de_arquivo(FILE *arquivo) {
+	int c;
+	do {
+		c = fgetc(arquivo);
+	} while (c != EOF && isspace(c));
+	if (c == EOF) return 1;
+	ungetc(c, arquivo);
+	return 0;
+}
+
+int main(void) {
+	FILE *arq;
+	int i, n, valor, soma;
+	char nome[80];
+	scanf("%d", &n);
+	arq = la_abra("/tmp/la_arquivo_teste.txt", "escrita");
+	fprintf(arq, "%s", "numeros ");
+	for (i = 1; i <= n; i++) {
+		fprintf(arq, "%d%s", i * 10, " ");
+	}
+	fclose(arq);
+	arq = la_abra("/tmp/la_arquivo_teste.txt", "leitura");
+	fscanf(arq, "%s", nome);
+	soma = 0;
+	while (!(la_fim_de_arquivo(arq))) {
+		fscanf(arq, "%d", &valor);
+		soma = soma + valor;
+	}
+	fclose(arq);
+	printf("%s%s%d", nome, ": ", soma);
+	return 0;
+}
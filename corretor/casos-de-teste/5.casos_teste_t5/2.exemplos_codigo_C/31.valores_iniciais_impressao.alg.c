@@ -0,0 +1,34 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+int total;
+char saudacao[80];
+float pesos[3];
+
+void mostrar(int n) {
+	int i = 0, contador = 0;
+	char nome[80];
+	strcpy(nome, "rotina");
+	int primos[4] = {2, 3, 5, 7};
+	for (i = 0; i <= 3; i++) {
+		contador = contador + primos[i];
+	}
+	printf("%s%s%d%s", nome, " ", n + contador, "\n");
+}
+
+int main(void) {
+	total = 10;
+	strcpy(saudacao, "ola");
+	pesos[0] = 0.5;
+	pesos[1] = 1.5;
+	pesos[2] = 2.0;
+	int i;
+	char letra = 'z';
+	float soma = 0.0;
+	for (i = 0; i <= 2; i++) {
+		soma = soma + pesos[i];
+	}
+	printf("%s%s%d%s%0.1f%s%c%s", saudacao, " ", total, " ", soma, " ", letra, "\n");
+	mostrar(total);
+	return 0;
+}
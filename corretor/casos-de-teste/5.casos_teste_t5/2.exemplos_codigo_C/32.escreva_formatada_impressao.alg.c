@@ -0,0 +1,21 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int main(void) {
+	int i, largura;
+	float x;
+	char nome[80];
+	scanf("%f", &x);
+	scanf("%s", nome);
+	
+	largura = 6;
+	printf("%s%8.2f%s", "[", x, "]\n");
+	printf("%s%0.3f%s", "[", x, "]\n");
+	printf("%s%*s%s", "[", largura, nome, "]\n");
+	for (i = 1; i <= 3; i++) {
+		printf("%s%*d%s", "[", largura - i, i * 7, "]");
+	}
+	printf("%s%*.*f%s", "\n[", largura + 4, largura / 2, x * 2, "]");
+	return 0;
+}
@@ -0,0 +1,43 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int fatorial(int n) {
+	if (!(n >= 0)) {
+		fputs("Linha 2: asserção falhou: n >= 0\n", stderr);
+		exit(1);
+	}
+	if (n <= 1) {
+		return 1;
+	}
+	return n * fatorial(n - 1);
+}
+
+int main(void) {
+	int i, n;
+	char nome[80];
+	scanf("%d", &n);
+	scanf("%s", nome);
+	
+	if (!(n > 0 && n < 10)) {
+		fputs("Linha 16: asserção falhou: n > 0 e n < 10\n", stderr);
+		exit(1);
+	}
+	if (!(strcmp(nome, "") != 0 || n == 0)) {
+		fputs("Linha 17: asserção falhou: nome <> \"\" ou n = 0\n", stderr);
+		exit(1);
+	}
+	for (i = 1; i <= n; i++) {
+		if (!(fatorial(i) % i == 0)) {
+			fputs("Linha 19: asserção falhou: fatorial(i) mod i = 0\n", stderr);
+			exit(1);
+		}
+		printf("%d%s", fatorial(i), " ");
+	}
+	if (!(!((strcmp(nome, "erro") == 0)))) {
+		fputs("Linha 22: asserção falhou: nao (nome = \"erro\")\n", stderr);
+		exit(1);
+	}
+	printf("%s%s%s", "\n", nome, " passou em todas as assercoes");
+	return 0;
+}
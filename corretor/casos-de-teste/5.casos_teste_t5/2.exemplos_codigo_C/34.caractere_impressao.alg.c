@@ -0,0 +1,20 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int main(void) {
+	char c, proxima;
+	int i;
+	scanf(" %c", &c);
+	printf("%s%c%c", "lido: ", c, '\n');
+	printf("%s%d%c", "codigo: ", (int) (c), '\n');
+	proxima = (char) ((int) (c) + 1);
+	printf("%s%c%c", "proxima: ", proxima, '\n');
+	if (c >= 'a' && c <= 'z') {
+		printf("%s", "minuscula\n");
+	}
+	for (i = 0; i <= 4; i++) {
+		printf("%c", (char) ((int) ('A') + i));
+	}
+	return 0;
+}
@@ -0,0 +1,16 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <math.h>
+
+int main(void) {
+	int a, b;
+	scanf("%d", &a);
+	scanf("%d", &b);
+	
+	printf("%d%s%d%s%d%s", a / b, " ", a % b, " ", a % b, "\n");
+	printf("%d%s%d%s", -a / b, " ", -a % b, "\n");
+	printf("%0.0f%s%0.0f%s%0.2f%s", pow(a, 2), " ", pow(2, b), " ", pow(2, -1), "\n");
+	printf("%d%s%d%s%0.0f", (a + b) / 2 * 3, " ", (a + b) % (b - 1), " ", pow(2, pow(3, 2)));
+	return 0;
+}
@@ -0,0 +1,51 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+typedef struct No {
+	int valor;
+	struct No *prox;
+} No;
+typedef struct Pessoa {
+	char nome[80];
+	struct Pessoa *chefe;
+	struct Equipe *time;
+} Pessoa;
+typedef struct Equipe {
+	char nome[80];
+	Pessoa *lider;
+} Equipe;
+
+int main(void) {
+	No a, b, c;
+	No *atual;
+	int soma;
+	Pessoa ana, bia;
+	Equipe grupo;
+	scanf("%d", &a.valor);
+	scanf("%d", &b.valor);
+	
+	scanf("%d", &c.valor);
+	
+	a.prox = &b;
+	b.prox = &c;
+	c.prox = &a;
+	atual = &a;
+	soma = 0;
+	while (atual->valor != 0) {
+		soma = soma + atual->valor;
+		printf("%d%s", atual->valor, " ");
+		atual->valor = 0;
+		atual = atual->prox;
+	}
+	printf("%s%d%s", "= ", soma, "\n");
+	printf("%d%s", a.prox->prox->valor, "\n");
+	strcpy(ana.nome, "ana");
+	strcpy(bia.nome, "bia");
+	strcpy(grupo.nome, "compiladores");
+	grupo.lider = &ana;
+	ana.time = &grupo;
+	bia.time = &grupo;
+	bia.chefe = bia.time->lider;
+	printf("%s%s%s%s%s", bia.nome, " responde a ", bia.chefe->nome, " em ", bia.chefe->time->nome);
+	return 0;
+}
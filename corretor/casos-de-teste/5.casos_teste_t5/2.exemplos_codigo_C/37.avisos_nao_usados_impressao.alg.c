@@ -0,0 +1,22 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#define LIMITE 10
+
+#define USADA 2
+typedef struct Sobra {
+	int campo;
+} Sobra;
+
+void mostrar(int valor, float ignorado) {
+	char local[80];
+	printf("%d", valor * USADA);
+}
+
+int main(void) {
+	int x, esquecida;
+	x = 1;
+	mostrar(x, 2.0);
+	return 0;
+}
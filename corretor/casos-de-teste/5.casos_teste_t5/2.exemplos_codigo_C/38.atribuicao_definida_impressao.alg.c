@@ -0,0 +1,26 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int main(void) {
+	int n, resultado, i;
+	scanf("%d", &n);
+	switch (n) {
+		case 1:
+			resultado = 10;
+			break;
+		case 2:
+		case 3:
+		case 4:
+		case 5:
+			resultado = 20;
+			break;
+		default:
+			resultado = 0;
+	}
+	for (i = 1; i <= n; i++) {
+		resultado = resultado + i;
+	}
+	printf("%d", resultado);
+	return 0;
+}
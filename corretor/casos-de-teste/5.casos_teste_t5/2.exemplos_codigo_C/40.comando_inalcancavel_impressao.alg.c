@@ -0,0 +1,19 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int absoluto(int n) {
+	if (n < 0) {
+		return -n;
+		printf("%s", "nunca impresso");
+	}
+	return n;
+	printf("%s", "nunca impresso");
+}
+
+int main(void) {
+	int n;
+	scanf("%d", &n);
+	printf("%d%s%d", absoluto(n), " ", absoluto(-n));
+	return 0;
+}
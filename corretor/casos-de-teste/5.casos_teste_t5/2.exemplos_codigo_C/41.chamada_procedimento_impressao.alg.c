@@ -0,0 +1,29 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+int chamadas;
+
+void linha(int largura, char simbolo) {
+	int i;
+	for (i = 1; i <= largura; i++) {
+		printf("%c", simbolo);
+	}
+	printf("%s", "\n");
+	chamadas = chamadas + 1;
+}
+
+int registrar(int n) {
+	chamadas = chamadas + n;
+	return chamadas;
+}
+
+int main(void) {
+	int n;
+	chamadas = 0;
+	scanf("%d", &n);
+	linha(n, '*');
+	linha(n * 2, '-');
+	registrar(10);
+	printf("%d", chamadas);
+	return 0;
+}
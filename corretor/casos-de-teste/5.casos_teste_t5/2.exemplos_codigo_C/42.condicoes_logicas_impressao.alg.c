@@ -0,0 +1,47 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+typedef enum { parado, andando, correndo } Estado;
+
+int main(void) {
+	int n, passos;
+	int continuar, par;
+	Estado estado;
+	scanf("%d", &n);
+	continuar = 1;
+	passos = 0;
+	while (continuar) {
+		par = n % 2 == 0;
+		if (par) {
+			n = n / 2;
+		} else {
+			n = 3 * n + 1;
+		}
+		passos = passos + 1;
+		continuar = n != 1;
+	}
+	printf("%d%s", passos, "\n");
+	estado = andando;
+	do {
+		switch (estado) {
+			case parado:
+				printf("%s", "parado\n");
+				break;
+			case andando:
+				printf("%s", "andando\n");
+				break;
+			case correndo:
+				printf("%s", "correndo\n");
+				break;
+		}
+		estado = (Estado) ((int) (estado) + 1);
+	} while (estado == correndo || 0);
+	switch ('b') {
+		case 'a':
+			printf("%s", "a");
+			break;
+		default:
+			printf("%s", "outro");
+	}
+	return 0;
+}
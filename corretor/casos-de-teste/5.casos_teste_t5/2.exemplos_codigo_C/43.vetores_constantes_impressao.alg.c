@@ -0,0 +1,30 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#define TAM 4
+
+#define LINHAS 2
+
+int main(void) {
+	int v[TAM];
+	int m[LINHAS][TAM];
+	int i, j, soma;
+	for (i = 0; i <= TAM - 1; i++) {
+		scanf("%d", &v[i]);
+	}
+	for (i = 0; i <= LINHAS - 1; i++) {
+		for (j = 0; j <= TAM - 1; j++) {
+			m[i][j] = v[j] * (i + 1);
+		}
+	}
+	soma = 0;
+	for (i = 0; i <= LINHAS - 1; i++) {
+		for (j = 0; j <= TAM - 1; j++) {
+			soma = soma + m[i][j];
+		}
+	}
+	printf("%d%s", soma, "\n");
+	printf("%d%s%d%s", m[1][TAM - 1], " ", v[0], "\n");
+	return 0;
+}
@@ -0,0 +1,47 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+typedef struct ponto {
+	int x, y;
+} ponto;
+
+void trocar(int *a, int *b) {
+	int t;
+	t = (*a);
+	(*a) = (*b);
+	(*b) = t;
+}
+
+void mover(ponto *p, int dx) {
+	(*p).x = (*p).x + dx;
+	(*p).y = (*p).y + dx;
+}
+
+void dobrar(int v[3]) {
+	int i;
+	for (i = 0; i <= 2; i++) {
+		v[i] = v[i] * 2;
+	}
+}
+
+int main(void) {
+	int a, b;
+	ponto p;
+	int v[3];
+	scanf("%d", &a);
+	scanf("%d", &b);
+	
+	trocar(&a, &b);
+	printf("%d%s%d%s", a, " ", b, "\n");
+	p.x = 1;
+	p.y = 2;
+	mover(&p, 3);
+	printf("%d%s%d%s", p.x, " ", p.y, "\n");
+	v[0] = 1;
+	v[1] = 2;
+	v[2] = 3;
+	dobrar(v);
+	trocar(&v[0], &v[2]);
+	printf("%d%s%d%s%d%s", v[0], " ", v[1], " ", v[2], "\n");
+	return 0;
+}
@@ -0,0 +1,42 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+typedef enum { vermelho, verde, azul } cor;
+
+int main(void) {
+	int n, i;
+	cor c;
+	scanf("%d", &n);
+	for (i = 0; i <= n; i++) {
+		switch (i) {
+			case 0:
+				printf("%s", "zero ");
+				break;
+			case 1:
+			case 2:
+			case 3:
+			case 5:
+				printf("%s", "baixo ");
+				break;
+			case 4:
+			case 6:
+			case 7:
+				printf("%s", "medio ");
+				break;
+			default:
+				printf("%s", "alto ");
+		}
+	}
+	printf("%s", "\n");
+	c = azul;
+	switch (c) {
+		case vermelho:
+			printf("%s", "r\n");
+			break;
+		case verde:
+		case azul:
+			printf("%s", "gb\n");
+			break;
+	}
+	return 0;
+}
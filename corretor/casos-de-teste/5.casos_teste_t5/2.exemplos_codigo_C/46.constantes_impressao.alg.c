@@ -0,0 +1,23 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#define PI 3.14
+
+#define N 3
+
+#define SAUDACAO "ola"
+
+int main(void) {
+	float raio;
+	int v[N];
+	int i;
+	scanf("%f", &raio);
+	printf("%s%s", SAUDACAO, "\n");
+	printf("%f%s", PI * raio * raio, "\n");
+	for (i = 0; i <= N - 1; i++) {
+		v[i] = i * N;
+	}
+	printf("%d%s", v[N - 1], "\n");
+	return 0;
+}